@@ -0,0 +1,641 @@
+//! Compares two [`OpenApi`] documents, to find (breaking) changes in an API.
+//!
+//! Every difference that is found is returned as a [`Change`], which contains the
+//! [JSON Pointer](https://datatracker.ietf.org/doc/html/rfc6901) to the changed location and
+//! whether the change can break existing clients.
+//!
+//! # Examples
+//!
+//! ```rust
+//! use okapi::diff::ChangeKind;
+//! use okapi::openapi3::{OpenApi, Operation, PathItem};
+//!
+//! let mut old = OpenApi::new();
+//! old.paths.insert(
+//!     "/users".to_owned(),
+//!     PathItem {
+//!         get: Some(Operation::default()),
+//!         post: Some(Operation::default()),
+//!         ..Default::default()
+//!     },
+//! );
+//! let mut new = old.clone();
+//! new.paths.get_mut("/users").unwrap().post = None;
+//!
+//! let changes = old.diff(&new);
+//! assert_eq!(changes.len(), 1);
+//! assert_eq!(changes[0].kind, ChangeKind::OperationRemoved);
+//! assert!(changes[0].breaking);
+//! assert_eq!(changes[0].pointer, "/paths/~1users/post");
+//! ```
+
+use crate::openapi3::{
+    Components, MediaType, OpenApi, Operation, Parameter, ParameterValue, PathItem, RefOr,
+    Response, SecurityRequirement,
+};
+use crate::validate::escape_pointer_token;
+use crate::Map;
+use schemars::schema::{InstanceType, Schema, SchemaObject, SingleOrVec};
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use std::collections::BTreeSet;
+use std::fmt;
+
+/// A difference between two [`OpenApi`] documents.
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize, Serialize)]
+pub struct Change {
+    /// What kind of change was found.
+    pub kind: ChangeKind,
+    /// `true` if the change can break existing clients of the API.
+    pub breaking: bool,
+    /// JSON Pointer to the location of the change. For removals this points into the old
+    /// document, otherwise into the new document.
+    pub pointer: String,
+    /// Human readable description of the change.
+    pub msg: String,
+}
+
+/// The different kinds of changes that are reported by [`diff`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Deserialize, Serialize)]
+pub enum ChangeKind {
+    /// A path was removed. Always breaking.
+    PathRemoved,
+    /// A path was added.
+    PathAdded,
+    /// An operation was removed from a path. Always breaking.
+    OperationRemoved,
+    /// An operation was added to an existing path.
+    OperationAdded,
+    /// A required parameter was added. Always breaking.
+    RequiredParameterAdded,
+    /// An optional parameter was added.
+    OptionalParameterAdded,
+    /// An optional parameter became required. Always breaking.
+    ParameterMadeRequired,
+    /// Values were removed from an `enum`.
+    /// Breaking for values sent by clients (parameters and request bodies).
+    EnumValuesRemoved,
+    /// Values were added to an `enum`.
+    /// Breaking for values returned to clients (responses).
+    EnumValuesAdded,
+    /// A response (status code) was removed. Always breaking.
+    ResponseRemoved,
+    /// A response (status code) was added.
+    ResponseAdded,
+    /// The `type` of a schema (or a property of a schema) changed.
+    /// Not breaking if the new type only allows more values for requests, or less values for
+    /// responses.
+    SchemaTypeChanged,
+    /// The security requirements of an operation changed.
+    /// Breaking if security is required where it was not required before, or if a way to
+    /// authenticate was removed.
+    SecurityChanged,
+}
+
+impl fmt::Display for Change {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let compatibility = if self.breaking {
+            "Breaking"
+        } else {
+            "Non-breaking"
+        };
+        write!(
+            f,
+            "{} change at `{}`: {}",
+            compatibility, self.pointer, self.msg
+        )
+    }
+}
+
+impl OpenApi {
+    /// Compare this (old) document with a new version of it. See [`diff`].
+    pub fn diff(&self, new: &OpenApi) -> Vec<Change> {
+        diff(self, new)
+    }
+}
+
+/// Compare the `old` and the `new` `OpenApi` document and return all changes to:
+/// - Paths and operations (added or removed).
+/// - Parameters (added, or made required).
+/// - Responses (status codes added or removed).
+/// - The `enum` values and `type` of schemas used by parameters, request bodies and responses,
+///   including the properties and items of those schemas.
+/// - The security requirements of operations (including the global security requirements).
+///
+/// Webhooks are not compared. Returns an empty list if no changes were found.
+pub fn diff(old: &OpenApi, new: &OpenApi) -> Vec<Change> {
+    let mut differ = Differ {
+        old,
+        new,
+        changes: Vec::new(),
+    };
+    differ.paths();
+    differ.changes
+}
+
+/// If a schema is used by values sent by the client or by values returned to the client.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Direction {
+    Request,
+    Response,
+}
+
+struct Differ<'a> {
+    old: &'a OpenApi,
+    new: &'a OpenApi,
+    changes: Vec<Change>,
+}
+
+impl<'a> Differ<'a> {
+    fn change(&mut self, kind: ChangeKind, breaking: bool, pointer: String, msg: String) {
+        self.changes.push(Change {
+            kind,
+            breaking,
+            pointer,
+            msg,
+        });
+    }
+
+    fn paths(&mut self) {
+        for (path, old_item) in &self.old.paths {
+            let pointer = format!("/paths/{}", escape_pointer_token(path));
+            match self.new.paths.get(path) {
+                Some(new_item) => self.path_item(old_item, new_item, &pointer),
+                None => self.change(
+                    ChangeKind::PathRemoved,
+                    true,
+                    pointer,
+                    format!("The path `{}` was removed.", path),
+                ),
+            }
+        }
+        for path in self.new.paths.keys() {
+            if !self.old.paths.contains_key(path) {
+                self.change(
+                    ChangeKind::PathAdded,
+                    false,
+                    format!("/paths/{}", escape_pointer_token(path)),
+                    format!("The path `{}` was added.", path),
+                );
+            }
+        }
+    }
+
+    fn path_item(&mut self, old_item: &PathItem, new_item: &PathItem, pointer: &str) {
+        let new_operations: Map<&str, &Operation> = new_item.operations().collect();
+        let old_operations: Map<&str, &Operation> = old_item.operations().collect();
+        for (method, old_op) in &old_operations {
+            let pointer = format!("{}/{}", pointer, method);
+            match new_operations.get(method) {
+                Some(new_op) => self.operation(old_item, old_op, new_item, new_op, &pointer),
+                None => self.change(
+                    ChangeKind::OperationRemoved,
+                    true,
+                    pointer,
+                    format!("The `{}` operation was removed.", method),
+                ),
+            }
+        }
+        for method in new_operations.keys() {
+            if !old_operations.contains_key(method) {
+                self.change(
+                    ChangeKind::OperationAdded,
+                    false,
+                    format!("{}/{}", pointer, method),
+                    format!("The `{}` operation was added.", method),
+                );
+            }
+        }
+    }
+
+    fn operation(
+        &mut self,
+        old_item: &PathItem,
+        old_op: &Operation,
+        new_item: &PathItem,
+        new_op: &Operation,
+        pointer: &str,
+    ) {
+        self.parameters(old_item, old_op, new_item, new_op, pointer);
+        if let (Some(old_body), Some(new_body)) = (&old_op.request_body, &new_op.request_body) {
+            let old_body = resolve(old_body, components(self.old).map(|c| &c.request_bodies));
+            let new_body = resolve(new_body, components(self.new).map(|c| &c.request_bodies));
+            if let (Some(old_body), Some(new_body)) = (old_body, new_body) {
+                self.content(
+                    &old_body.content,
+                    &new_body.content,
+                    Direction::Request,
+                    &format!("{}/requestBody/content", pointer),
+                );
+            }
+        }
+        self.responses(old_op, new_op, pointer);
+        self.security(old_op, new_op, pointer);
+    }
+
+    fn parameters(
+        &mut self,
+        old_item: &PathItem,
+        old_op: &Operation,
+        new_item: &PathItem,
+        new_op: &Operation,
+        pointer: &str,
+    ) {
+        let old_params = parameters(self.old, old_item, old_op, pointer);
+        let new_params = parameters(self.new, new_item, new_op, pointer);
+        for (key, (param_pointer, new_param)) in new_params {
+            let name = format!("`{}` ({})", new_param.name, new_param.location);
+            match old_params.get(&key).map(|(_, old_param)| old_param) {
+                None if new_param.required => self.change(
+                    ChangeKind::RequiredParameterAdded,
+                    true,
+                    param_pointer,
+                    format!("The required parameter {} was added.", name),
+                ),
+                None => self.change(
+                    ChangeKind::OptionalParameterAdded,
+                    false,
+                    param_pointer,
+                    format!("The optional parameter {} was added.", name),
+                ),
+                Some(old_param) => {
+                    if new_param.required && !old_param.required {
+                        self.change(
+                            ChangeKind::ParameterMadeRequired,
+                            true,
+                            param_pointer.clone(),
+                            format!("The parameter {} is now required.", name),
+                        );
+                    }
+                    match (&old_param.value, &new_param.value) {
+                        (
+                            ParameterValue::Schema {
+                                schema: old_schema, ..
+                            },
+                            ParameterValue::Schema {
+                                schema: new_schema, ..
+                            },
+                        ) => self.schema(
+                            old_schema,
+                            new_schema,
+                            Direction::Request,
+                            &format!("{}/schema", param_pointer),
+                            &mut BTreeSet::new(),
+                        ),
+                        (
+                            ParameterValue::Content {
+                                content: old_content,
+                            },
+                            ParameterValue::Content {
+                                content: new_content,
+                            },
+                        ) => self.content(
+                            old_content,
+                            new_content,
+                            Direction::Request,
+                            &format!("{}/content", param_pointer),
+                        ),
+                        _ => {}
+                    }
+                }
+            }
+        }
+    }
+
+    fn responses(&mut self, old_op: &Operation, new_op: &Operation, pointer: &str) {
+        let old_responses = responses(old_op);
+        let new_responses = responses(new_op);
+        for (status, old_response) in &old_responses {
+            let response_pointer =
+                format!("{}/responses/{}", pointer, escape_pointer_token(status));
+            match new_responses.get(status) {
+                None => self.change(
+                    ChangeKind::ResponseRemoved,
+                    true,
+                    response_pointer,
+                    format!("The `{}` response was removed.", status),
+                ),
+                Some(new_response) => {
+                    let old_response =
+                        resolve(old_response, components(self.old).map(|c| &c.responses));
+                    let new_response =
+                        resolve(new_response, components(self.new).map(|c| &c.responses));
+                    if let (Some(old_response), Some(new_response)) = (old_response, new_response) {
+                        self.content(
+                            &old_response.content,
+                            &new_response.content,
+                            Direction::Response,
+                            &format!("{}/content", response_pointer),
+                        );
+                    }
+                }
+            }
+        }
+        for status in new_responses.keys() {
+            if !old_responses.contains_key(status) {
+                self.change(
+                    ChangeKind::ResponseAdded,
+                    false,
+                    format!("{}/responses/{}", pointer, escape_pointer_token(status)),
+                    format!("The `{}` response was added.", status),
+                );
+            }
+        }
+    }
+
+    fn security(&mut self, old_op: &Operation, new_op: &Operation, pointer: &str) {
+        let old_security = old_op.security.as_ref().unwrap_or(&self.old.security);
+        let new_security = new_op.security.as_ref().unwrap_or(&self.new.security);
+        let old_security = normalize_security(old_security);
+        let new_security = normalize_security(new_security);
+        if old_security == new_security {
+            return;
+        }
+        // Requiring security where it was not required before, or removing a way to
+        // authenticate breaks clients.
+        let breaking = (old_security.is_empty() && !new_security.is_empty())
+            || (!new_security.is_empty() && !old_security.is_subset(&new_security));
+        self.change(
+            ChangeKind::SecurityChanged,
+            breaking,
+            format!("{}/security", pointer),
+            "The security requirements changed.".to_owned(),
+        );
+    }
+
+    fn content(
+        &mut self,
+        old_content: &Map<String, MediaType>,
+        new_content: &Map<String, MediaType>,
+        direction: Direction,
+        pointer: &str,
+    ) {
+        for (media_type, old_media) in old_content {
+            let new_media = match new_content.get(media_type) {
+                Some(new_media) => new_media,
+                None => continue,
+            };
+            if let (Some(old_schema), Some(new_schema)) = (&old_media.schema, &new_media.schema) {
+                self.schema(
+                    old_schema,
+                    new_schema,
+                    direction,
+                    &format!("{}/{}/schema", pointer, escape_pointer_token(media_type)),
+                    &mut BTreeSet::new(),
+                );
+            }
+        }
+    }
+
+    /// Compare 2 schemas, `visited` contains the pairs of references that are already compared,
+    /// to stop recursive schemas.
+    fn schema(
+        &mut self,
+        old_schema: &SchemaObject,
+        new_schema: &SchemaObject,
+        direction: Direction,
+        pointer: &str,
+        visited: &mut BTreeSet<(String, String)>,
+    ) {
+        if let (Some(old_ref), Some(new_ref)) = (&old_schema.reference, &new_schema.reference) {
+            if !visited.insert((old_ref.clone(), new_ref.clone())) {
+                return;
+            }
+        }
+        let old_schema = match resolve_schema(self.old, old_schema) {
+            Some(schema) => schema,
+            None => return,
+        };
+        let new_schema = match resolve_schema(self.new, new_schema) {
+            Some(schema) => schema,
+            None => return,
+        };
+
+        self.schema_type(old_schema, new_schema, direction, pointer);
+        self.schema_enum(old_schema, new_schema, direction, pointer);
+
+        if let (Some(old_object), Some(new_object)) = (&old_schema.object, &new_schema.object) {
+            for (name, old_property) in &old_object.properties {
+                if let (Schema::Object(old_property), Some(Schema::Object(new_property))) =
+                    (old_property, new_object.properties.get(name))
+                {
+                    self.schema(
+                        old_property,
+                        new_property,
+                        direction,
+                        &format!("{}/properties/{}", pointer, escape_pointer_token(name)),
+                        visited,
+                    );
+                }
+            }
+        }
+        if let (Some(old_array), Some(new_array)) = (&old_schema.array, &new_schema.array) {
+            if let (Some(SingleOrVec::Single(old_items)), Some(SingleOrVec::Single(new_items))) =
+                (&old_array.items, &new_array.items)
+            {
+                if let (Schema::Object(old_items), Schema::Object(new_items)) =
+                    (old_items.as_ref(), new_items.as_ref())
+                {
+                    self.schema(
+                        old_items,
+                        new_items,
+                        direction,
+                        &format!("{}/items", pointer),
+                        visited,
+                    );
+                }
+            }
+        }
+    }
+
+    fn schema_type(
+        &mut self,
+        old_schema: &SchemaObject,
+        new_schema: &SchemaObject,
+        direction: Direction,
+        pointer: &str,
+    ) {
+        let (old_types, new_types) = match (&old_schema.instance_type, &new_schema.instance_type) {
+            (Some(old_types), Some(new_types)) => {
+                (instance_types(old_types), instance_types(new_types))
+            }
+            _ => return,
+        };
+        if old_types == new_types {
+            return;
+        }
+        // Clients can keep sending the old types if more types are allowed,
+        // and can handle the returned values if less types are returned.
+        let breaking = match direction {
+            Direction::Request => !old_types.is_subset(&new_types),
+            Direction::Response => !new_types.is_subset(&old_types),
+        };
+        self.change(
+            ChangeKind::SchemaTypeChanged,
+            breaking,
+            format!("{}/type", pointer),
+            format!(
+                "The type changed from `{:?}` to `{:?}`.",
+                old_types, new_types
+            ),
+        );
+    }
+
+    fn schema_enum(
+        &mut self,
+        old_schema: &SchemaObject,
+        new_schema: &SchemaObject,
+        direction: Direction,
+        pointer: &str,
+    ) {
+        let (old_values, new_values) = match (&old_schema.enum_values, &new_schema.enum_values) {
+            (Some(old_values), Some(new_values)) => (old_values, new_values),
+            _ => return,
+        };
+        let removed: Vec<&Value> = old_values
+            .iter()
+            .filter(|value| !new_values.contains(value))
+            .collect();
+        let added: Vec<&Value> = new_values
+            .iter()
+            .filter(|value| !old_values.contains(value))
+            .collect();
+        if !removed.is_empty() {
+            self.change(
+                ChangeKind::EnumValuesRemoved,
+                direction == Direction::Request,
+                format!("{}/enum", pointer),
+                format!("The enum values {} were removed.", format_values(&removed)),
+            );
+        }
+        if !added.is_empty() {
+            self.change(
+                ChangeKind::EnumValuesAdded,
+                direction == Direction::Response,
+                format!("{}/enum", pointer),
+                format!("The enum values {} were added.", format_values(&added)),
+            );
+        }
+    }
+}
+
+fn components(spec: &OpenApi) -> Option<&Components> {
+    spec.components.as_ref()
+}
+
+/// Follow the references to components, until the object is found.
+fn resolve<'a, T>(
+    mut ref_or: &'a RefOr<T>,
+    components: Option<&'a Map<String, RefOr<T>>>,
+) -> Option<&'a T> {
+    // Limit the number of references to follow, in case they are recursive.
+    for _ in 0..32 {
+        match ref_or {
+            RefOr::Object(object) => return Some(object),
+            RefOr::Ref(reference) => {
+                let name = reference.reference.rsplit('/').next()?;
+                ref_or = components?.get(name)?;
+            }
+        }
+    }
+    None
+}
+
+/// Follow the reference of the schema to the schema in the components.
+fn resolve_schema<'a>(spec: &'a OpenApi, mut schema: &'a SchemaObject) -> Option<&'a SchemaObject> {
+    for _ in 0..32 {
+        match &schema.reference {
+            None => return Some(schema),
+            Some(reference) => {
+                let name = reference.strip_prefix("#/components/schemas/")?;
+                schema = components(spec)?.schemas.get(name)?;
+            }
+        }
+    }
+    None
+}
+
+/// Returns all parameters of the operation, including the parameters of the path item,
+/// by location and name. Together with the JSON Pointer to the parameter.
+fn parameters<'a>(
+    spec: &'a OpenApi,
+    path_item: &'a PathItem,
+    operation: &'a Operation,
+    operation_pointer: &str,
+) -> Map<(String, String), (String, &'a Parameter)> {
+    let component_parameters = components(spec).map(|c| &c.parameters);
+    let path_pointer = operation_pointer
+        .rsplit_once('/')
+        .map(|(path_pointer, _)| path_pointer)
+        .unwrap_or_default();
+    let path_item_parameters = path_item
+        .parameters
+        .iter()
+        .enumerate()
+        .map(|(index, parameter)| (format!("{}/parameters/{}", path_pointer, index), parameter));
+    let operation_parameters = operation
+        .parameters
+        .iter()
+        .enumerate()
+        .map(|(index, parameter)| {
+            (
+                format!("{}/parameters/{}", operation_pointer, index),
+                parameter,
+            )
+        });
+    // Operation parameters override path item parameters.
+    path_item_parameters
+        .chain(operation_parameters)
+        .filter_map(|(pointer, parameter)| {
+            let parameter = resolve(parameter, component_parameters)?;
+            let key = (parameter.location.clone(), parameter.name.clone());
+            Some((key, (pointer, parameter)))
+        })
+        .collect()
+}
+
+fn responses(operation: &Operation) -> Map<String, &RefOr<Response>> {
+    let mut responses: Map<String, &RefOr<Response>> = operation
+        .responses
+        .responses
+        .iter()
+        .map(|(status, response)| (status.clone(), response))
+        .collect();
+    if let Some(default) = &operation.responses.default {
+        responses.insert("default".to_owned(), default);
+    }
+    responses
+}
+
+fn normalize_security(security: &[SecurityRequirement]) -> BTreeSet<Vec<(String, Vec<String>)>> {
+    security
+        .iter()
+        .map(|requirement| {
+            let mut requirement: Vec<(String, Vec<String>)> = requirement
+                .iter()
+                .map(|(name, scopes)| {
+                    let mut scopes = scopes.clone();
+                    scopes.sort();
+                    (name.clone(), scopes)
+                })
+                .collect();
+            requirement.sort();
+            requirement
+        })
+        .collect()
+}
+
+fn instance_types(types: &SingleOrVec<InstanceType>) -> BTreeSet<InstanceType> {
+    match types {
+        SingleOrVec::Single(ty) => std::iter::once(**ty).collect(),
+        SingleOrVec::Vec(types) => types.iter().copied().collect(),
+    }
+}
+
+fn format_values(values: &[&Value]) -> String {
+    values
+        .iter()
+        .map(|value| format!("`{}`", value))
+        .collect::<Vec<_>>()
+        .join(", ")
+}
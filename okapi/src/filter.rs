@@ -0,0 +1,152 @@
+//! Filter the operations of an [`OpenApi`] document, for example to publish a public and an
+//! internal version of the same document.
+//!
+//! # Examples
+//!
+//! ```rust
+//! use okapi::filter::{has_tag, path_glob};
+//! use okapi::openapi3::{OpenApi, Operation, PathItem};
+//!
+//! let mut spec = OpenApi::new();
+//! for path in ["/users", "/internal/metrics"] {
+//!     let operation = Operation {
+//!         tags: vec!["public".to_owned()],
+//!         ..Default::default()
+//!     };
+//!     spec.paths.insert(
+//!         path.to_owned(),
+//!         PathItem {
+//!             get: Some(operation),
+//!             ..Default::default()
+//!         },
+//!     );
+//! }
+//!
+//! let is_internal = path_glob("/internal/**");
+//! spec.retain_operations(|path, method, op| {
+//!     has_tag("public")(path, method, op) && !is_internal(path, method, op)
+//! });
+//! assert_eq!(spec.paths.keys().collect::<Vec<_>>(), vec!["/users"]);
+//! ```
+
+use crate::openapi3::{OpenApi, Operation, SecurityRequirement};
+use serde_json::Value;
+use std::collections::BTreeSet;
+
+/// A predicate used to filter operations. It is called with the path, the lowercase HTTP method
+/// and the operation.
+pub type OperationPredicate<'a> = dyn Fn(&str, &str, &Operation) -> bool + 'a;
+
+impl OpenApi {
+    /// Only keep the operations for which the predicate returns `true`.
+    ///
+    /// The predicate is called with the path (or name of the webhook), the lowercase HTTP method
+    /// and the operation. Path items from which all operations are removed are removed as well.
+    /// Path items that did not contain any operation (for example a path item that only
+    /// contains a `$ref`) are kept.
+    ///
+    /// Tags and security schemes that were used by the removed operations, but are no longer used
+    /// by any of the remaining operations, are removed as well. Call
+    /// [`prune_unused_components`](OpenApi::prune_unused_components) afterwards to also remove the
+    /// other components that are no longer used.
+    pub fn retain_operations<F>(&mut self, mut predicate: F)
+    where
+        F: FnMut(&str, &str, &Operation) -> bool,
+    {
+        let (tags_before, schemes_before) = used_tags_and_security_schemes(self);
+        for path_items in [&mut self.paths, &mut self.webhooks] {
+            path_items.retain(|path, path_item| {
+                if path_item.operations().next().is_none() {
+                    return true;
+                }
+                for (method, operation) in path_item.operation_slots_mut() {
+                    if matches!(operation, Some(op) if !predicate(path, method, op)) {
+                        *operation = None;
+                    }
+                }
+                path_item.operations().next().is_some()
+            });
+        }
+        let (tags_after, schemes_after) = used_tags_and_security_schemes(self);
+
+        self.tags
+            .retain(|tag| !tags_before.contains(&tag.name) || tags_after.contains(&tag.name));
+        if let Some(components) = &mut self.components {
+            components
+                .security_schemes
+                .retain(|name, _| !schemes_before.contains(name) || schemes_after.contains(name));
+        }
+    }
+}
+
+/// Returns a predicate that matches operations that have the given tag.
+pub fn has_tag(tag: &str) -> impl Fn(&str, &str, &Operation) -> bool {
+    let tag = tag.to_owned();
+    move |_, _, operation| operation.tags.contains(&tag)
+}
+
+/// Returns a predicate that matches operations that have a path that matches the glob pattern.
+///
+/// In the pattern `*` matches any characters within a path segment, and `**` matches any
+/// characters including `/`. For example `/users/*` matches `/users/{id}` but not
+/// `/users/{id}/posts`, while `/users/**` matches both.
+pub fn path_glob(pattern: &str) -> impl Fn(&str, &str, &Operation) -> bool {
+    let pattern = pattern.to_owned();
+    move |path, _, _| glob_matches(&pattern, path)
+}
+
+/// Returns a predicate that matches operations that have the given `x-` extension,
+/// with any value except `false` or `null`.
+pub fn has_extension(name: &str) -> impl Fn(&str, &str, &Operation) -> bool {
+    let name = name.to_owned();
+    move |_, _, operation| {
+        !matches!(
+            operation.extensions.get(&name),
+            None | Some(Value::Null) | Some(Value::Bool(false))
+        )
+    }
+}
+
+/// Returns a predicate that matches operations that have the given `x-` extension with the
+/// given value.
+pub fn extension_equals(name: &str, value: Value) -> impl Fn(&str, &str, &Operation) -> bool {
+    let name = name.to_owned();
+    move |_, _, operation| operation.extensions.get(&name) == Some(&value)
+}
+
+/// Check if the path matches the glob pattern, see [`path_glob`] for the syntax.
+#[must_use]
+pub fn glob_matches(pattern: &str, path: &str) -> bool {
+    glob_matches_bytes(pattern.as_bytes(), path.as_bytes())
+}
+
+fn glob_matches_bytes(pattern: &[u8], path: &[u8]) -> bool {
+    match pattern {
+        [] => path.is_empty(),
+        [b'*', b'*', rest @ ..] => (0..=path.len()).any(|i| glob_matches_bytes(rest, &path[i..])),
+        [b'*', rest @ ..] => {
+            let segment_end = path.iter().position(|c| *c == b'/').unwrap_or(path.len());
+            (0..=segment_end).any(|i| glob_matches_bytes(rest, &path[i..]))
+        }
+        [c, rest @ ..] => path.first() == Some(c) && glob_matches_bytes(rest, &path[1..]),
+    }
+}
+
+/// Returns the names of the tags and security schemes used by the document.
+fn used_tags_and_security_schemes(spec: &OpenApi) -> (BTreeSet<String>, BTreeSet<String>) {
+    fn add_schemes(schemes: &mut BTreeSet<String>, security: &[SecurityRequirement]) {
+        schemes.extend(security.iter().flat_map(|req| req.keys().cloned()));
+    }
+    let mut tags = BTreeSet::new();
+    let mut schemes = BTreeSet::new();
+    add_schemes(&mut schemes, &spec.security);
+    for path_item in spec.paths.values().chain(spec.webhooks.values()) {
+        for (_, operation) in path_item.operations() {
+            tags.extend(operation.tags.iter().cloned());
+            if let Some(security) = &operation.security {
+                add_schemes(&mut schemes, security);
+            }
+        }
+    }
+    (tags, schemes)
+}
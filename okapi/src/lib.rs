@@ -4,11 +4,22 @@
 pub type Map<K, V> = schemars::Map<K, V>;
 pub type MapEntry<'a, K, V> = schemars::MapEntry<'a, K, V>;
 
+pub mod diff;
+pub mod filter;
 pub mod merge;
 pub mod openapi3;
+mod prune;
+pub mod v3_1;
+pub mod validate;
+pub mod visit;
+#[cfg(feature = "yaml")]
+mod yaml;
 
 /// Re-export the current version of `Schemars` used by `Okapi`.
 pub use schemars;
+/// Re-export the current version of `serde_yaml` used by `Okapi`.
+#[cfg(feature = "yaml")]
+pub use serde_yaml;
 
 /// Macro to crate an `okapi::Map` with a number of key-value pairs in it.
 ///
@@ -1,4 +1,8 @@
-use crate::openapi3::{Components, Info, OpenApi, PathItem, Responses, Tag};
+use crate::openapi3::{
+    Components, Info, OpenApi, Operation, PathItem, Responses, SecurityRequirement, Tag,
+};
+use crate::validate::escape_pointer_token;
+use crate::visit::{self, Visitor};
 use crate::{Map, MapEntry};
 use serde::{Deserialize, Serialize};
 use std::fmt;
@@ -7,20 +11,102 @@ use std::fmt::Display;
 #[derive(Debug, Clone, PartialEq, Eq, Deserialize, Serialize, Default)]
 pub struct MergeError {
     pub msg: String,
+    /// All conflicts that were found while merging.
+    /// Only set when merging using [`OnConflict::Error`].
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub conflicts: Vec<MergeConflict>,
 }
 
 impl Display for MergeError {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-        write!(f, "{}", self.msg)
+        write!(f, "{}", self.msg)?;
+        for conflict in &self.conflicts {
+            write!(f, "\n- {}", conflict)?;
+        }
+        Ok(())
     }
 }
 
+impl std::error::Error for MergeError {}
+
 impl MergeError {
     fn new<S: AsRef<str>>(msg: S) -> Self {
         MergeError {
             msg: msg.as_ref().to_owned(),
+            ..Default::default()
         }
     }
+
+    fn from_conflicts(conflicts: Vec<MergeConflict>) -> Self {
+        MergeError {
+            msg: format!(
+                "Found {} conflict(s) while merging OpenAPI specs:",
+                conflicts.len()
+            ),
+            conflicts,
+        }
+    }
+}
+
+/// Two specs define the same key with different values.
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize, Serialize, Default)]
+pub struct MergeConflict {
+    /// The part of the spec the key is defined in.
+    /// For example `schemas`, `responses`, `security_schemes` or `operations`.
+    /// The vendor extensions of the spec are in `extensions`, those of the components, paths and
+    /// webhooks in `component_extensions`, `path_extensions` and `webhook_extensions`.
+    pub section: String,
+    /// The key that is defined twice.
+    /// For operations this is the method followed by the path, for example `get /v1/user`.
+    /// For the extensions of a path or webhook this is the name of the extension followed by the
+    /// path or webhook, for example `x-internal /v1/user`.
+    pub key: String,
+    /// The path prefix of the spec that defined the key first.
+    /// This is `None` when the key was already defined in the spec that is merged into.
+    pub first_source: Option<String>,
+    /// The path prefix of the spec that defined the key again.
+    pub second_source: String,
+}
+
+impl Display for MergeConflict {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "`{}` in {} is defined differently by `{}` and `{}`",
+            self.key,
+            self.section,
+            self.first_source.as_deref().unwrap_or("<existing spec>"),
+            self.second_source,
+        )
+    }
+}
+
+/// Options to control how OpenAPI specs are merged.
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub struct MergeOptions {
+    /// What to do when 2 specs define the same component, operation or extension
+    /// with a different value.
+    pub on_conflict: OnConflict,
+}
+
+/// What to do when 2 specs define the same key with a different value.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum OnConflict {
+    /// Return a [`MergeError`] that lists all conflicts.
+    Error,
+    /// Keep the value of the first spec and log a warning.
+    #[default]
+    KeepFirst,
+    /// Use the value of the last spec and log a warning.
+    KeepLast,
+    /// Rename the conflicting component of the spec that is merged in, using the path prefix
+    /// it is mounted on. For example the `Error` schema of the spec mounted on `/post` is
+    /// renamed to `post_Error`. All references (`$ref`) to the component (and security
+    /// requirements for security schemes) in that spec are changed to the new name.
+    ///
+    /// Operations and extensions can not be renamed, so for those the first value is kept
+    /// and a warning is logged.
+    Rename,
 }
 
 impl OpenApi {
@@ -32,11 +118,36 @@ impl OpenApi {
 
 /// Marge the list of all specs together into on big OpenApi object.
 pub fn marge_spec_list<S: Display>(spec_list: &[(S, OpenApi)]) -> Result<OpenApi, MergeError> {
+    marge_spec_list_with_options(spec_list, &MergeOptions::default())
+}
+
+/// Marge the list of all specs together into on big OpenApi object, using the given options.
+///
+/// When using [`OnConflict::Error`] all specs are merged first, so the returned
+/// [`MergeError`] contains all conflicts between all specs.
+pub fn marge_spec_list_with_options<S: Display>(
+    spec_list: &[(S, OpenApi)],
+    options: &MergeOptions,
+) -> Result<OpenApi, MergeError> {
     let mut openapi_docs = OpenApi::new();
-    for (path_prefix, spec) in spec_list {
-        merge_specs(&mut openapi_docs, path_prefix, spec)?;
+    let mut conflicts = Vec::new();
+    for (index, (path_prefix, spec)) in spec_list.iter().enumerate() {
+        let mut merger = Merger::new(options, path_prefix);
+        merger.specs(&mut openapi_docs, path_prefix, spec)?;
+        for mut conflict in merger.conflicts {
+            // Find the spec that defined the key first.
+            conflict.first_source = spec_list[..index]
+                .iter()
+                .find(|(prefix, spec)| defines_key(spec, prefix, &conflict))
+                .map(|(prefix, _)| prefix.to_string());
+            conflicts.push(conflict);
+        }
+    }
+    if conflicts.is_empty() {
+        Ok(openapi_docs)
+    } else {
+        Err(MergeError::from_conflicts(conflicts))
     }
-    Ok(openapi_docs)
 }
 
 /// Merge the given OpenAPI spec into the current one.
@@ -45,22 +156,24 @@ pub fn merge_specs<S: Display>(
     path_prefix: &S,
     s2: &OpenApi,
 ) -> Result<(), MergeError> {
-    // Check if specs are same version
-    if s1.openapi != s2.openapi {
-        return Err(MergeError::new("OpenAPI specs version do not match."));
-    }
-    merge_spec_info(&mut s1.info, &s2.info)?;
-    merge_vec(&mut s1.servers, &s2.servers);
-    merge_paths(&mut s1.paths, path_prefix, &s2.paths)?;
-    merge_components(&mut s1.components, &s2.components)?;
-    // This is a `Vec<Map<String, _>` but just merge the `Vec` items together.
-    // Do not merge the `Map` items together.
-    merge_vec(&mut s1.security, &s2.security);
-    merge_tags(&mut s1.tags, &s2.tags)?;
-    // Replace the external_docs info as 1 block, so don't mix
-    merge_option(&mut s1.external_docs, &s2.external_docs);
-    merge_map(&mut s1.extensions, &s2.extensions, "extensions");
-    Ok(())
+    merge_specs_with_options(s1, path_prefix, s2, &MergeOptions::default())
+}
+
+/// Merge the given OpenAPI spec into the current one, using the given options.
+pub fn merge_specs_with_options<S: Display>(
+    s1: &mut OpenApi,
+    path_prefix: &S,
+    s2: &OpenApi,
+    options: &MergeOptions,
+) -> Result<(), MergeError> {
+    let mut merger = Merger::new(options, path_prefix);
+    merger.specs(s1, path_prefix, s2)?;
+    merger.finish()
+}
+
+/// Check if the spec does not contain any paths, webhooks or components.
+fn is_empty_spec(spec: &OpenApi) -> bool {
+    spec.paths.is_empty() && spec.webhooks.is_empty() && spec.components.is_none()
 }
 
 pub fn merge_spec_info(s1: &mut Info, s2: &Info) -> Result<(), MergeError> {
@@ -84,57 +197,24 @@ pub fn merge_paths<S: Display>(
     path_prefix: &S,
     s2: &Map<String, PathItem>,
 ) -> Result<(), MergeError> {
-    // Add all s2 values
-    // (if key does not already exists)
-    for (key, value) in s2 {
-        let new_key = if key.starts_with('/') {
-            // Check if both the prefix ends with a `/` and key starts with one.
-            let mut path_prefix = path_prefix.to_string();
-            if path_prefix.ends_with('/') {
-                // Avoid a double `/`
-                path_prefix.pop();
-                format!("{}{}", path_prefix, key)
-            } else {
-                format!("{}{}", path_prefix, key)
-            }
-        } else {
-            log::error!(
-                "All routes should have a leading '/' but non found in `{}`.",
-                key
-            );
-            format!("{}/{}", path_prefix, key)
-        };
-        match s1.entry(new_key) {
-            MapEntry::Occupied(mut entry) => {
-                // Merge `PathItem` so get/post/put routes are getting merged
-                let current_value = entry.get_mut();
-                merge_path_item(current_value, value)?;
-            }
-            MapEntry::Vacant(entry) => {
-                entry.insert(value.clone());
-            }
-        }
-    }
-    Ok(())
+    let mut merger = Merger::new(&MergeOptions::default(), path_prefix);
+    merger.paths(s1, path_prefix, s2)
 }
 
-pub fn merge_path_item(s1: &mut PathItem, s2: &PathItem) -> Result<(), MergeError> {
-    merge_opt_string(&mut s1.reference, &s2.reference);
-    merge_opt_string(&mut s1.summary, &s2.summary);
-    merge_opt_string(&mut s1.description, &s2.description);
+/// Merge `Map<String, PathItem>`/`&Map<String, PathItem>` of webhooks:
+/// Merge together. If key already exists, merge the `PathItem`s.
+/// Webhooks are not mounted, so no prefix is added.
+pub fn merge_webhooks(
+    s1: &mut Map<String, PathItem>,
+    s2: &Map<String, PathItem>,
+) -> Result<(), MergeError> {
+    let mut merger = Merger::new(&MergeOptions::default(), &"");
+    merger.webhooks(s1, s2)
+}
 
-    merge_option(&mut s1.get, &s2.get);
-    merge_option(&mut s1.put, &s2.put);
-    merge_option(&mut s1.post, &s2.post);
-    merge_option(&mut s1.delete, &s2.delete);
-    merge_option(&mut s1.options, &s2.options);
-    merge_option(&mut s1.head, &s2.head);
-    merge_option(&mut s1.patch, &s2.patch);
-    merge_option(&mut s1.trace, &s2.trace);
-
-    merge_option(&mut s1.servers, &s2.servers);
-    merge_vec(&mut s1.parameters, &s2.parameters);
-    merge_map(&mut s1.extensions, &s2.extensions, "extensions");
+pub fn merge_path_item(s1: &mut PathItem, s2: &PathItem) -> Result<(), MergeError> {
+    let mut merger = Merger::new(&MergeOptions::default(), &"");
+    merger.path_item(s1, s2, "");
     Ok(())
 }
 
@@ -142,32 +222,9 @@ pub fn merge_components(
     s1: &mut Option<Components>,
     s2: &Option<Components>,
 ) -> Result<(), MergeError> {
-    if s1.is_none() {
-        *s1 = s2.clone();
-        Ok(())
-    } else if s2.is_none() {
-        // Use/keep s1
-        Ok(())
-    } else {
-        if let Some(s1) = s1 {
-            let s2 = s2.as_ref().unwrap();
-            merge_map(&mut s1.schemas, &s2.schemas, "schemas");
-            merge_map(&mut s1.responses, &s2.responses, "responses");
-            merge_map(&mut s1.parameters, &s2.parameters, "parameters");
-            merge_map(&mut s1.examples, &s2.examples, "examples");
-            merge_map(&mut s1.request_bodies, &s2.request_bodies, "request_bodies");
-            merge_map(&mut s1.headers, &s2.headers, "headers");
-            merge_map(
-                &mut s1.security_schemes,
-                &s2.security_schemes,
-                "security_schemes",
-            );
-            merge_map(&mut s1.links, &s2.links, "links");
-            merge_map(&mut s1.callbacks, &s2.callbacks, "callbacks");
-            merge_map(&mut s1.extensions, &s2.extensions, "extensions");
-        }
-        Ok(())
-    }
+    let mut merger = Merger::new(&MergeOptions::default(), &"");
+    merger.components(s1, s2);
+    Ok(())
 }
 
 pub fn merge_tags(s1: &mut Vec<Tag>, s2: &[Tag]) -> Result<Vec<Tag>, MergeError> {
@@ -271,34 +328,549 @@ pub fn merge_map<T: Clone + PartialEq + std::fmt::Debug>(
     s2: &Map<String, T>,
     name: &str,
 ) {
+    let mut merger = Merger::new(&MergeOptions::default(), &"");
+    merger.map(s1, s2, name);
+}
+
+/// Merge `Vec<_>`/`&Vec<_>`:
+/// Append lists, `s1` first and `s2` after that.
+pub fn merge_vec<T: Clone>(s1: &mut Vec<T>, s2: &[T]) {
     // Add all s2 values
-    // (if key does not already exists)
-    for (key, value) in s2 {
-        if let Some(s1_value) = s1.get(key) {
-            // Check if this is the same element
-            if value != s1_value {
+    for value in s2 {
+        s1.push(value.clone());
+    }
+}
+
+/// Combine the path prefix with the path of a route.
+fn prefixed_path<S: Display>(path_prefix: &S, key: &str) -> String {
+    if key.starts_with('/') {
+        // Check if both the prefix ends with a `/` and key starts with one.
+        let mut path_prefix = path_prefix.to_string();
+        if path_prefix.ends_with('/') {
+            // Avoid a double `/`
+            path_prefix.pop();
+            format!("{}{}", path_prefix, key)
+        } else {
+            format!("{}{}", path_prefix, key)
+        }
+    } else {
+        log::error!(
+            "All routes should have a leading '/' but non found in `{}`.",
+            key
+        );
+        format!("{}/{}", path_prefix, key)
+    }
+}
+
+/// Check if the spec (mounted at `path_prefix`) defines the key of the conflict.
+/// This handles every section a `Merger` reports conflicts for.
+fn defines_key<S: Display>(spec: &OpenApi, path_prefix: &S, conflict: &MergeConflict) -> bool {
+    let key = &conflict.key;
+    match conflict.section.as_str() {
+        "operations" => {
+            return spec.paths.iter().any(|(path, path_item)| {
+                path_item.operations().any(|(method, _)| {
+                    format!("{} {}", method, prefixed_path(path_prefix, path)) == *key
+                })
+            })
+        }
+        "webhooks" => {
+            return spec.webhooks.iter().any(|(name, path_item)| {
+                path_item
+                    .operations()
+                    .any(|(method, _)| format!("{} {}", method, name) == *key)
+            })
+        }
+        "extensions" => return spec.extensions.contains_key(key),
+        section @ ("path_extensions" | "webhook_extensions") => {
+            let (extension, key) = match key.split_once(' ') {
+                Some(split) => split,
+                None => return false,
+            };
+            return if section == "path_extensions" {
+                spec.paths.iter().any(|(path, path_item)| {
+                    prefixed_path(path_prefix, path) == key
+                        && path_item.extensions.contains_key(extension)
+                })
+            } else {
+                matches!(spec.webhooks.get(key), Some(path_item) if path_item.extensions.contains_key(extension))
+            };
+        }
+        _ => {}
+    }
+    let components = match &spec.components {
+        Some(components) => components,
+        None => return false,
+    };
+    match conflict.section.as_str() {
+        "schemas" => components.schemas.contains_key(key),
+        "responses" => components.responses.contains_key(key),
+        "parameters" => components.parameters.contains_key(key),
+        "examples" => components.examples.contains_key(key),
+        "request_bodies" => components.request_bodies.contains_key(key),
+        "headers" => components.headers.contains_key(key),
+        "security_schemes" => components.security_schemes.contains_key(key),
+        "links" => components.links.contains_key(key),
+        "callbacks" => components.callbacks.contains_key(key),
+        "component_extensions" => components.extensions.contains_key(key),
+        _ => false,
+    }
+}
+
+/// Merges specs using the given `MergeOptions`, and keeps track of all conflicts found.
+struct Merger {
+    on_conflict: OnConflict,
+    /// The path prefix of the spec that is being merged into the other one.
+    source: String,
+    conflicts: Vec<MergeConflict>,
+}
+
+impl Merger {
+    fn new<S: Display>(options: &MergeOptions, source: &S) -> Self {
+        Merger {
+            on_conflict: options.on_conflict,
+            source: source.to_string(),
+            conflicts: Vec::new(),
+        }
+    }
+
+    /// Return an error if conflicts were found.
+    fn finish(self) -> Result<(), MergeError> {
+        if self.conflicts.is_empty() {
+            Ok(())
+        } else {
+            Err(MergeError::from_conflicts(self.conflicts))
+        }
+    }
+
+    /// Handle a conflict, returns `true` if the value of `s1` should be replaced by `s2`.
+    fn conflict<T: std::fmt::Debug>(&mut self, section: &str, key: &str, s1: &T, s2: &T) -> bool {
+        match self.on_conflict {
+            OnConflict::Error => {
+                self.conflicts.push(MergeConflict {
+                    section: section.to_owned(),
+                    key: key.to_owned(),
+                    first_source: None,
+                    second_source: self.source.clone(),
+                });
+                false
+            }
+            OnConflict::KeepFirst | OnConflict::KeepLast | OnConflict::Rename => {
                 log::warn!(
                     "Found conflicting {} keys while merging, \
                     they have the same name but different values for `{}`:\n\
                     {:?}\n\
                     {:?}",
-                    name,
+                    section,
                     key,
-                    s1_value,
-                    value,
+                    s1,
+                    s2,
                 );
+                self.on_conflict == OnConflict::KeepLast
+            }
+        }
+    }
+
+    fn specs<S: Display>(
+        &mut self,
+        s1: &mut OpenApi,
+        path_prefix: &S,
+        s2: &OpenApi,
+    ) -> Result<(), MergeError> {
+        // Check if specs are same version
+        if s1.openapi != s2.openapi {
+            if is_empty_spec(s1) {
+                // An empty spec (like the one `marge_spec_list` starts with) takes over the version
+                // of the first spec that is merged into it.
+                s1.openapi = s2.openapi.clone();
+            } else {
+                return Err(MergeError::new("OpenAPI specs version do not match."));
             }
+        }
+        let renamed;
+        let s2 = if self.on_conflict == OnConflict::Rename {
+            renamed = rename_conflicting_components(s1, path_prefix, s2);
+            &renamed
         } else {
-            s1.insert(key.clone(), value.clone());
+            s2
+        };
+        merge_spec_info(&mut s1.info, &s2.info)?;
+        merge_opt_string(&mut s1.json_schema_dialect, &s2.json_schema_dialect);
+        merge_vec(&mut s1.servers, &s2.servers);
+        self.paths(&mut s1.paths, path_prefix, &s2.paths)?;
+        self.webhooks(&mut s1.webhooks, &s2.webhooks)?;
+        self.components(&mut s1.components, &s2.components);
+        // This is a `Vec<Map<String, _>` but just merge the `Vec` items together.
+        // Do not merge the `Map` items together.
+        merge_vec(&mut s1.security, &s2.security);
+        merge_tags(&mut s1.tags, &s2.tags)?;
+        // Replace the external_docs info as 1 block, so don't mix
+        merge_option(&mut s1.external_docs, &s2.external_docs);
+        self.map(&mut s1.extensions, &s2.extensions, "extensions");
+        Ok(())
+    }
+
+    fn paths<S: Display>(
+        &mut self,
+        s1: &mut Map<String, PathItem>,
+        path_prefix: &S,
+        s2: &Map<String, PathItem>,
+    ) -> Result<(), MergeError> {
+        // Add all s2 values
+        // (if key does not already exists)
+        for (key, value) in s2 {
+            let new_key = prefixed_path(path_prefix, key);
+            match s1.entry(new_key) {
+                MapEntry::Occupied(mut entry) => {
+                    // Merge `PathItem` so get/post/put routes are getting merged
+                    let key = entry.key().clone();
+                    let current_value = entry.get_mut();
+                    self.path_item(current_value, value, &key);
+                }
+                MapEntry::Vacant(entry) => {
+                    entry.insert(value.clone());
+                }
+            }
+        }
+        Ok(())
+    }
+
+    fn webhooks(
+        &mut self,
+        s1: &mut Map<String, PathItem>,
+        s2: &Map<String, PathItem>,
+    ) -> Result<(), MergeError> {
+        for (key, value) in s2 {
+            match s1.entry(key.clone()) {
+                MapEntry::Occupied(mut entry) => {
+                    let mut merger = Merger::new(
+                        &MergeOptions {
+                            on_conflict: self.on_conflict,
+                        },
+                        &self.source,
+                    );
+                    merger.path_item(entry.get_mut(), value, key);
+                    // Report webhook conflicts in their own section.
+                    for mut conflict in merger.conflicts {
+                        match conflict.section.as_str() {
+                            "operations" => conflict.section = "webhooks".to_owned(),
+                            "path_extensions" => conflict.section = "webhook_extensions".to_owned(),
+                            _ => {}
+                        }
+                        self.conflicts.push(conflict);
+                    }
+                }
+                MapEntry::Vacant(entry) => {
+                    entry.insert(value.clone());
+                }
+            }
+        }
+        Ok(())
+    }
+
+    fn path_item(&mut self, s1: &mut PathItem, s2: &PathItem, path: &str) {
+        merge_opt_string(&mut s1.reference, &s2.reference);
+        merge_opt_string(&mut s1.summary, &s2.summary);
+        merge_opt_string(&mut s1.description, &s2.description);
+
+        self.operation(&mut s1.get, &s2.get, "get", path);
+        self.operation(&mut s1.put, &s2.put, "put", path);
+        self.operation(&mut s1.post, &s2.post, "post", path);
+        self.operation(&mut s1.delete, &s2.delete, "delete", path);
+        self.operation(&mut s1.options, &s2.options, "options", path);
+        self.operation(&mut s1.head, &s2.head, "head", path);
+        self.operation(&mut s1.patch, &s2.patch, "patch", path);
+        self.operation(&mut s1.trace, &s2.trace, "trace", path);
+
+        merge_option(&mut s1.servers, &s2.servers);
+        merge_vec(&mut s1.parameters, &s2.parameters);
+        // The same extension can be used by every path, so the path is added to the key.
+        let mut merger = Merger::new(
+            &MergeOptions {
+                on_conflict: self.on_conflict,
+            },
+            &self.source,
+        );
+        merger.map(&mut s1.extensions, &s2.extensions, "path_extensions");
+        for mut conflict in merger.conflicts {
+            conflict.key = format!("{} {}", conflict.key, path);
+            self.conflicts.push(conflict);
+        }
+    }
+
+    /// Merge `Option<Operation>`/`&Option<Operation>`:
+    /// - If one is `None`: Use other
+    /// - If both are `Some` and different: Handle the conflict.
+    fn operation(
+        &mut self,
+        s1: &mut Option<Operation>,
+        s2: &Option<Operation>,
+        method: &str,
+        path: &str,
+    ) {
+        match (s1.as_ref(), s2) {
+            (None, _) => *s1 = s2.clone(),
+            (Some(op1), Some(op2)) if op1 != op2 => {
+                let key = format!("{} {}", method, path);
+                if self.conflict("operations", &key, op1, op2) {
+                    *s1 = s2.clone();
+                }
+            }
+            _ => {}
+        }
+    }
+
+    fn components(&mut self, s1: &mut Option<Components>, s2: &Option<Components>) {
+        match (s1, s2) {
+            (s1 @ None, _) => *s1 = s2.clone(),
+            // Use/keep s1
+            (Some(_), None) => {}
+            (Some(s1), Some(s2)) => {
+                self.map(&mut s1.schemas, &s2.schemas, "schemas");
+                self.map(&mut s1.responses, &s2.responses, "responses");
+                self.map(&mut s1.parameters, &s2.parameters, "parameters");
+                self.map(&mut s1.examples, &s2.examples, "examples");
+                self.map(&mut s1.request_bodies, &s2.request_bodies, "request_bodies");
+                self.map(&mut s1.headers, &s2.headers, "headers");
+                self.map(
+                    &mut s1.security_schemes,
+                    &s2.security_schemes,
+                    "security_schemes",
+                );
+                self.map(&mut s1.links, &s2.links, "links");
+                self.map(&mut s1.callbacks, &s2.callbacks, "callbacks");
+                self.map(&mut s1.extensions, &s2.extensions, "component_extensions");
+            }
+        }
+    }
+
+    /// Merge `Map<String, _>`/`&Map<String, _>`:
+    /// Merge together. If key already exists with a different value, handle the conflict.
+    fn map<T: Clone + PartialEq + std::fmt::Debug>(
+        &mut self,
+        s1: &mut Map<String, T>,
+        s2: &Map<String, T>,
+        name: &str,
+    ) {
+        // Add all s2 values
+        // (if key does not already exists)
+        for (key, value) in s2 {
+            if let Some(s1_value) = s1.get(key) {
+                // Check if this is the same element
+                if value != s1_value && self.conflict(name, key, s1_value, value) {
+                    s1.insert(key.clone(), value.clone());
+                }
+            } else {
+                s1.insert(key.clone(), value.clone());
+            }
         }
     }
 }
 
-/// Merge `Vec<_>`/`&Vec<_>`:
-/// Append lists, `s1` first and `s2` after that.
-pub fn merge_vec<T: Clone>(s1: &mut Vec<T>, s2: &[T]) {
-    // Add all s2 values
-    for value in s2 {
-        s1.push(value.clone());
+/// Returns a copy of `s2` in which all components that conflict with the components of `s1` are
+/// renamed, and all references to them are changed to the new name.
+fn rename_conflicting_components<S: Display>(
+    s1: &OpenApi,
+    path_prefix: &S,
+    s2: &OpenApi,
+) -> OpenApi {
+    let c1 = match (&s1.components, &s2.components) {
+        (Some(c1), Some(_)) => c1,
+        _ => return s2.clone(),
+    };
+    let prefix = component_prefix(&path_prefix.to_string());
+    // Renaming a component changes the components that reference it, which can then conflict
+    // too. So rename until no more components are renamed. Every round starts from `s2`, as
+    // the new names of one round might be the old names of a later round.
+    let mut renames = RenameReferences::default();
+    let mut rounds = 0;
+    loop {
+        let mut candidate = s2.clone();
+        if !renames.references.is_empty() {
+            visit::visit_openapi(&mut renames, &mut candidate);
+        }
+        let mut next = RenameReferences::default();
+        let security_schemes = next.components(
+            &renames,
+            c1,
+            candidate.components.as_mut().unwrap(),
+            &prefix,
+        );
+        rounds += 1;
+        // Components are never un-renamed in a later round. The limit protects against
+        // components that reference each other and keep switching between two names.
+        if next.references != renames.references && rounds <= renames.references.len() + 1 {
+            renames = next;
+            continue;
+        }
+        for (old, new) in &next.references {
+            log::debug!(
+                "Renamed conflicting component `{}` to `{}` while merging.",
+                old,
+                new
+            );
+        }
+        // Security schemes are not referenced using a `$ref`, but by name.
+        if !security_schemes.is_empty() {
+            rename_security_requirements(&mut candidate.security, &security_schemes);
+            for path_item in candidate
+                .paths
+                .values_mut()
+                .chain(candidate.webhooks.values_mut())
+            {
+                for (_, operation) in path_item.operations_mut() {
+                    if let Some(security) = &mut operation.security {
+                        rename_security_requirements(security, &security_schemes);
+                    }
+                }
+            }
+        }
+        return candidate;
+    }
+}
+
+/// Create the prefix for renamed components from the path prefix, `/v1/post` becomes `v1_post`.
+fn component_prefix(path_prefix: &str) -> String {
+    path_prefix
+        .split(|c: char| !c.is_ascii_alphanumeric() && c != '_' && c != '-')
+        .filter(|part| !part.is_empty())
+        .collect::<Vec<_>>()
+        .join("_")
+}
+
+fn rename_security_requirements(
+    requirements: &mut [SecurityRequirement],
+    renames: &Map<String, String>,
+) {
+    for requirement in requirements {
+        *requirement = std::mem::take(requirement)
+            .into_iter()
+            .map(|(name, scopes)| match renames.get(&name) {
+                Some(new_name) => (new_name.clone(), scopes),
+                None => (name, scopes),
+            })
+            .collect();
+    }
+}
+
+/// [`Visitor`] that changes the references to renamed components.
+#[derive(Default)]
+struct RenameReferences {
+    /// Map from the old reference to the new reference.
+    references: Map<String, String>,
+}
+
+impl RenameReferences {
+    /// Rename the components in `c2` that conflict with the ones in `c1`, or that were renamed
+    /// in the `previous` round. Returns a map from the old name to the new name of the renamed
+    /// security schemes.
+    fn components(
+        &mut self,
+        previous: &Self,
+        c1: &Components,
+        c2: &mut Components,
+        prefix: &str,
+    ) -> Map<String, String> {
+        self.section(previous, &c1.schemas, &mut c2.schemas, "schemas", prefix);
+        self.section(
+            previous,
+            &c1.responses,
+            &mut c2.responses,
+            "responses",
+            prefix,
+        );
+        self.section(
+            previous,
+            &c1.parameters,
+            &mut c2.parameters,
+            "parameters",
+            prefix,
+        );
+        self.section(previous, &c1.examples, &mut c2.examples, "examples", prefix);
+        self.section(
+            previous,
+            &c1.request_bodies,
+            &mut c2.request_bodies,
+            "requestBodies",
+            prefix,
+        );
+        self.section(previous, &c1.headers, &mut c2.headers, "headers", prefix);
+        let security_schemes = self.section(
+            previous,
+            &c1.security_schemes,
+            &mut c2.security_schemes,
+            "securitySchemes",
+            prefix,
+        );
+        self.section(previous, &c1.links, &mut c2.links, "links", prefix);
+        self.section(
+            previous,
+            &c1.callbacks,
+            &mut c2.callbacks,
+            "callbacks",
+            prefix,
+        );
+        security_schemes
+    }
+
+    /// Rename all components in `s2` that conflict with the ones in `s1`, or that were renamed
+    /// in the `previous` round. The references in `s2` should already be renamed using
+    /// `previous`, so the components are compared as they will be merged.
+    /// Returns a map from the old name to the new name.
+    fn section<T: Clone + PartialEq>(
+        &mut self,
+        previous: &Self,
+        s1: &Map<String, T>,
+        s2: &mut Map<String, T>,
+        section: &str,
+        prefix: &str,
+    ) -> Map<String, String> {
+        let reference =
+            |key: &str| format!("#/components/{}/{}", section, escape_pointer_token(key));
+        let conflicting: Vec<String> = s2
+            .iter()
+            .filter(|(key, value)| {
+                previous.references.contains_key(&reference(key))
+                    || matches!(s1.get(*key), Some(s1_value) if s1_value != *value)
+            })
+            .map(|(key, _)| key.clone())
+            .collect();
+        let mut renamed = Map::new();
+        for key in conflicting {
+            let value = match s2.remove(&key) {
+                Some(value) => value,
+                None => continue,
+            };
+            let new_key = (1..)
+                .map(|index| match (prefix.is_empty(), index) {
+                    (false, 1) => format!("{}_{}", prefix, key),
+                    (false, _) => format!("{}_{}_{}", prefix, key, index),
+                    (true, _) => format!("{}_{}", key, index + 1),
+                })
+                .find(|new_key| {
+                    // The same component might already be merged with this name before.
+                    s1.get(new_key).map(|v| v == &value).unwrap_or(true)
+                        && !s2.contains_key(new_key)
+                })
+                .expect("There is always an unused name.");
+            self.references.insert(reference(&key), reference(&new_key));
+            s2.insert(new_key.clone(), value);
+            renamed.insert(key, new_key);
+        }
+        renamed
+    }
+}
+
+impl Visitor for RenameReferences {
+    fn visit_reference(&mut self, reference: &mut String) {
+        for (old, new) in &self.references {
+            if let Some(rest) = reference.strip_prefix(old.as_str()) {
+                // Also change references to a part of the component.
+                if rest.is_empty() || rest.starts_with('/') {
+                    *reference = format!("{}{}", new, rest);
+                    return;
+                }
+            }
+        }
     }
 }
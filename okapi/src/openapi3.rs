@@ -40,6 +40,28 @@ impl OpenApi {
     pub fn default_version() -> String {
         "3.0.0".to_owned()
     }
+
+    /// Create a new OpenAPI 3.1 document, using the default `jsonSchemaDialect`.
+    pub fn new_v3_1() -> Self {
+        OpenApi {
+            openapi: Self::v3_1_version(),
+            json_schema_dialect: Some(crate::v3_1::DEFAULT_JSON_SCHEMA_DIALECT.to_owned()),
+            ..Default::default()
+        }
+    }
+
+    pub fn v3_1_version() -> String {
+        "3.1.0".to_owned()
+    }
+
+    /// Returns `true` if this document is written for OpenAPI 3.1 (or later 3.x versions).
+    pub fn is_v3_1(&self) -> bool {
+        let mut version = self.openapi.split('.');
+        match (version.next(), version.next()) {
+            (Some("3"), Some(minor)) => matches!(minor.parse::<u32>(), Ok(minor) if minor >= 1),
+            _ => false,
+        }
+    }
 }
 
 #[derive(Debug, Clone, PartialEq, Deserialize, Serialize, Default)]
@@ -48,9 +70,19 @@ impl OpenApi {
 pub struct OpenApi {
     pub openapi: String,
     pub info: Info,
+    /// The default value for the `$schema` keyword within Schema Objects.
+    /// Only available in OpenAPI 3.1.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub json_schema_dialect: Option<String>,
     #[serde(default, skip_serializing_if = "Vec::is_empty")]
     pub servers: Vec<Server>,
+    /// Required in OpenAPI 3.0, optional in OpenAPI 3.1.
+    #[serde(default)]
     pub paths: Map<String, PathItem>,
+    /// Incoming webhooks that may be received as part of this API.
+    /// Only available in OpenAPI 3.1.
+    #[serde(default, skip_serializing_if = "Map::is_empty")]
+    pub webhooks: Map<String, PathItem>,
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub components: Option<Components>,
     #[serde(default, skip_serializing_if = "Vec::is_empty")]
@@ -167,6 +199,45 @@ pub struct PathItem {
     pub extensions: Object,
 }
 
+impl PathItem {
+    /// Returns all the operations of this `PathItem`, together with their (lowercase) HTTP method.
+    pub fn operations(&self) -> impl Iterator<Item = (&'static str, &Operation)> {
+        [
+            ("get", &self.get),
+            ("put", &self.put),
+            ("post", &self.post),
+            ("delete", &self.delete),
+            ("options", &self.options),
+            ("head", &self.head),
+            ("patch", &self.patch),
+            ("trace", &self.trace),
+        ]
+        .into_iter()
+        .filter_map(|(method, operation)| operation.as_ref().map(|op| (method, op)))
+    }
+
+    /// Returns all the operations of this `PathItem`, together with their (lowercase) HTTP method.
+    pub fn operations_mut(&mut self) -> impl Iterator<Item = (&'static str, &mut Operation)> {
+        self.operation_slots_mut()
+            .into_iter()
+            .filter_map(|(method, operation)| operation.as_mut().map(|op| (method, op)))
+    }
+
+    /// Returns the fields of all HTTP methods, so operations can be added or removed.
+    pub(crate) fn operation_slots_mut(&mut self) -> [(&'static str, &mut Option<Operation>); 8] {
+        [
+            ("get", &mut self.get),
+            ("put", &mut self.put),
+            ("post", &mut self.post),
+            ("delete", &mut self.delete),
+            ("options", &mut self.options),
+            ("head", &mut self.head),
+            ("patch", &mut self.patch),
+            ("trace", &mut self.trace),
+        ]
+    }
+}
+
 #[derive(Debug, Clone, PartialEq, Deserialize, Serialize, Default)]
 #[cfg_attr(feature = "impl_json_schema", derive(JsonSchema))]
 #[serde(rename_all = "camelCase")]
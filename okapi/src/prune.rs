@@ -0,0 +1,107 @@
+//! Removes the components that are not used by any path or webhook.
+
+use crate::openapi3::{Components, OpenApi};
+use crate::visit::{self, Visitor};
+use crate::Map;
+use std::collections::BTreeSet;
+
+impl OpenApi {
+    /// Remove all `schemas`, `responses`, `parameters`, `examples`, `request_bodies`, `headers`,
+    /// `links` and `callbacks` from the components that are not referenced (directly or through
+    /// other components) by the `paths` or `webhooks` of this document.
+    ///
+    /// The `security_schemes` are always kept, as they are referenced by name from the security
+    /// requirements instead of by a `$ref`.
+    pub fn prune_unused_components(&mut self) {
+        let components = match &mut self.components {
+            Some(components) => components,
+            None => return,
+        };
+        let mut collector = CollectReferences::default();
+        for path_item in self.paths.values_mut().chain(self.webhooks.values_mut()) {
+            visit::visit_path_item(&mut collector, path_item);
+        }
+
+        // Follow the references between components, until no new components are found.
+        let mut used = BTreeSet::new();
+        while let Some(reference) = collector.references.pop() {
+            let (section, name) = match component_of_reference(&reference) {
+                Some(component) => component,
+                None => continue,
+            };
+            if used.insert((section.clone(), name.clone())) {
+                let mut component = single_component(components, &section, &name);
+                visit::visit_components(&mut collector, &mut component);
+            }
+        }
+
+        let is_used =
+            |section: &str, name: &str| used.contains(&(section.to_owned(), name.to_owned()));
+        components
+            .schemas
+            .retain(|name, _| is_used("schemas", name));
+        components
+            .responses
+            .retain(|name, _| is_used("responses", name));
+        components
+            .parameters
+            .retain(|name, _| is_used("parameters", name));
+        components
+            .examples
+            .retain(|name, _| is_used("examples", name));
+        components
+            .request_bodies
+            .retain(|name, _| is_used("requestBodies", name));
+        components
+            .headers
+            .retain(|name, _| is_used("headers", name));
+        components.links.retain(|name, _| is_used("links", name));
+        components
+            .callbacks
+            .retain(|name, _| is_used("callbacks", name));
+    }
+}
+
+/// [`Visitor`] that collects all references it visits.
+#[derive(Default)]
+struct CollectReferences {
+    references: Vec<String>,
+}
+
+impl Visitor for CollectReferences {
+    fn visit_reference(&mut self, reference: &mut String) {
+        self.references.push(reference.clone());
+    }
+}
+
+/// Returns the section and name of the component, if the reference points to (a part of)
+/// a component in the same document. For example `#/components/schemas/Error` returns
+/// `("schemas", "Error")`.
+fn component_of_reference(reference: &str) -> Option<(String, String)> {
+    let mut tokens = reference.strip_prefix("#/components/")?.split('/');
+    let section = tokens.next()?;
+    let name = tokens.next()?.replace("~1", "/").replace("~0", "~");
+    Some((section.to_owned(), name))
+}
+
+/// Returns `Components` that only contain the given component, so it can be visited.
+fn single_component(components: &Components, section: &str, name: &str) -> Components {
+    fn copy<T: Clone>(from: &Map<String, T>, to: &mut Map<String, T>, name: &str) {
+        if let Some(value) = from.get(name) {
+            to.insert(name.to_owned(), value.clone());
+        }
+    }
+    let mut single = Components::default();
+    match section {
+        "schemas" => copy(&components.schemas, &mut single.schemas, name),
+        "responses" => copy(&components.responses, &mut single.responses, name),
+        "parameters" => copy(&components.parameters, &mut single.parameters, name),
+        "examples" => copy(&components.examples, &mut single.examples, name),
+        "requestBodies" => copy(&components.request_bodies, &mut single.request_bodies, name),
+        "headers" => copy(&components.headers, &mut single.headers, name),
+        "links" => copy(&components.links, &mut single.links, name),
+        "callbacks" => copy(&components.callbacks, &mut single.callbacks, name),
+        _ => {}
+    }
+    single
+}
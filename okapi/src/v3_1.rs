@@ -0,0 +1,87 @@
+//! Helpers to create [OpenAPI 3.1](https://spec.openapis.org/oas/v3.1.0) documents.
+//!
+//! OpenAPI 3.1 uses JSON Schema 2020-12 for its Schema Objects, so some of the OpenAPI 3.0
+//! extensions to JSON Schema should be replaced by their JSON Schema equivalent:
+//! - `nullable: true` is replaced by adding `"null"` to the `type` of the schema.
+//! - `example` is replaced by the `examples` array.
+
+use crate::openapi3::OpenApi;
+use crate::visit::{self, Visitor};
+use schemars::schema::{InstanceType, Schema, SchemaObject, SingleOrVec};
+use serde_json::Value;
+
+/// The default JSON Schema dialect used by OpenAPI 3.1 Schema Objects.
+pub const DEFAULT_JSON_SCHEMA_DIALECT: &str = "https://spec.openapis.org/oas/3.1/dialect/base";
+
+impl OpenApi {
+    /// Upgrade this document to OpenAPI 3.1.
+    ///
+    /// This sets the `openapi` version and the `jsonSchemaDialect` (if not set yet), and
+    /// converts all JSON Schemas (in the components and in the paths and webhooks) from the
+    /// OpenAPI 3.0 dialect to the OpenAPI 3.1 dialect.
+    /// Calling this on a document that is already written for OpenAPI 3.1 is allowed.
+    pub fn upgrade_to_v3_1(&mut self) {
+        if !self.is_v3_1() {
+            self.openapi = Self::v3_1_version();
+        }
+        self.json_schema_dialect
+            .get_or_insert_with(|| DEFAULT_JSON_SCHEMA_DIALECT.to_owned());
+        visit::visit_openapi(&mut UpgradeSchemas, self);
+    }
+}
+
+/// [`Visitor`] that converts the OpenAPI 3.0 `nullable` and `example` schema properties to
+/// the equivalent OpenAPI 3.1 (JSON Schema 2020-12) properties.
+#[derive(Debug, Clone, Default)]
+pub struct UpgradeSchemas;
+
+impl Visitor for UpgradeSchemas {
+    fn visit_schema_object(&mut self, schema: &mut SchemaObject) {
+        visit::visit_schema_object(self, schema);
+
+        if let Some(example) = schema.extensions.remove("example") {
+            let examples = &mut schema.metadata().examples;
+            if !examples.contains(&example) {
+                examples.insert(0, example);
+            }
+        }
+
+        if schema.extensions.remove("nullable") == Some(Value::Bool(true)) {
+            make_nullable(schema);
+        }
+    }
+}
+
+/// Allow `null` as a value of the schema.
+fn make_nullable(schema: &mut SchemaObject) {
+    if let Some(enum_values) = &mut schema.enum_values {
+        if !enum_values.contains(&Value::Null) {
+            enum_values.push(Value::Null);
+        }
+    }
+    match &mut schema.instance_type {
+        Some(SingleOrVec::Single(ty)) if **ty != InstanceType::Null => {
+            schema.instance_type = Some(vec![**ty, InstanceType::Null].into());
+        }
+        Some(SingleOrVec::Vec(types)) => {
+            if !types.contains(&InstanceType::Null) {
+                types.push(InstanceType::Null);
+            }
+        }
+        // Already nullable, or the enum values (that now include `null`) define the type.
+        Some(SingleOrVec::Single(_)) => {}
+        None if schema.enum_values.is_some() => {}
+        None => {
+            // The type is defined by a reference or subschemas, so allow either that or `null`.
+            // Keep the metadata (title, description,...) on the outer schema.
+            let metadata = schema.metadata.take();
+            let inner = std::mem::take(schema);
+            let null_schema = SchemaObject {
+                instance_type: Some(InstanceType::Null.into()),
+                ..Default::default()
+            };
+            schema.metadata = metadata;
+            schema.subschemas().any_of = Some(vec![Schema::Object(inner), null_schema.into()]);
+        }
+    }
+}
@@ -0,0 +1,395 @@
+//! Checks an [`OpenApi`] document for common mistakes.
+//!
+//! Every problem that is found is returned as a [`Diagnostic`], which contains the
+//! [JSON Pointer](https://datatracker.ietf.org/doc/html/rfc6901) to the location of the problem
+//! in the (serialized) document.
+//!
+//! # Examples
+//!
+//! ```rust
+//! use okapi::openapi3::{OpenApi, Operation, PathItem};
+//! use okapi::validate::DiagnosticKind;
+//!
+//! let mut spec = OpenApi::new();
+//! spec.paths.insert(
+//!     "/users/{id}".to_owned(),
+//!     PathItem {
+//!         get: Some(Operation::default()),
+//!         ..Default::default()
+//!     },
+//! );
+//!
+//! let diagnostics = spec.validate();
+//! assert_eq!(diagnostics.len(), 1);
+//! assert_eq!(diagnostics[0].kind, DiagnosticKind::MissingPathParameter);
+//! assert_eq!(diagnostics[0].pointer, "/paths/~1users~1{id}/get");
+//! ```
+
+use crate::openapi3::{OpenApi, Operation, Parameter, PathItem, RefOr, SecurityRequirement};
+use crate::Map;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use std::fmt;
+
+/// A problem found in an [`OpenApi`] document.
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize, Serialize)]
+pub struct Diagnostic {
+    /// What kind of problem was found.
+    pub kind: DiagnosticKind,
+    /// How severe the problem is.
+    pub severity: Severity,
+    /// JSON Pointer to the location of the problem in the document.
+    pub pointer: String,
+    /// Human readable description of the problem.
+    pub msg: String,
+}
+
+/// The different kinds of problems that are reported by [`validate`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Deserialize, Serialize)]
+pub enum DiagnosticKind {
+    /// A local `$ref` points to a location that does not exist in the document.
+    DanglingRef,
+    /// The same `operationId` is used by more than one operation.
+    DuplicateOperationId,
+    /// A templated path parameter (`/users/{id}`) is not defined as `in: path` parameter.
+    MissingPathParameter,
+    /// A security requirement names a security scheme that is not defined in the components.
+    UndefinedSecurityScheme,
+    /// A response has an empty description.
+    EmptyResponseDescription,
+}
+
+/// The severity of a [`Diagnostic`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Deserialize, Serialize)]
+pub enum Severity {
+    /// The document is valid, but might not render or generate clients correctly.
+    Warning,
+    /// The document is not valid according to the OpenAPI specification.
+    Error,
+}
+
+impl fmt::Display for Diagnostic {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{:?} at `{}`: {}", self.severity, self.pointer, self.msg)
+    }
+}
+
+impl Diagnostic {
+    fn new(kind: DiagnosticKind, pointer: String, msg: String) -> Self {
+        let severity = match kind {
+            DiagnosticKind::EmptyResponseDescription => Severity::Warning,
+            _ => Severity::Error,
+        };
+        Diagnostic {
+            kind,
+            severity,
+            pointer,
+            msg,
+        }
+    }
+}
+
+impl OpenApi {
+    /// Check this document for common mistakes. See [`validate`].
+    pub fn validate(&self) -> Vec<Diagnostic> {
+        validate(self)
+    }
+}
+
+/// Check the `OpenApi` document for:
+/// - Dangling local `$ref`s in `RefOr`/`Ref`, path items and schemas.
+/// - Duplicate `operationId`s.
+/// - Templated path parameters without a matching `in: path` parameter.
+/// - Security requirements naming undefined `security_schemes`.
+/// - Responses with empty descriptions (reported as [`Severity::Warning`]).
+///
+/// Returns an empty list if no problems were found.
+pub fn validate(spec: &OpenApi) -> Vec<Diagnostic> {
+    let mut diagnostics = Vec::new();
+    check_references(spec, &mut diagnostics);
+    check_operation_ids(spec, &mut diagnostics);
+    check_path_parameters(spec, &mut diagnostics);
+    check_security_requirements(spec, &mut diagnostics);
+    check_response_descriptions(spec, &mut diagnostics);
+    diagnostics
+}
+
+/// Escape a single reference token of a JSON Pointer.
+pub(crate) fn escape_pointer_token(token: &str) -> String {
+    token.replace('~', "~0").replace('/', "~1")
+}
+
+/// Returns all the operations in the paths and webhooks, with the JSON Pointer to the operation.
+fn operations(spec: &OpenApi) -> Vec<(String, &PathItem, &Operation)> {
+    let mut result = Vec::new();
+    for (section, path_items) in [("paths", &spec.paths), ("webhooks", &spec.webhooks)] {
+        for (path, path_item) in path_items {
+            for (method, operation) in path_item.operations() {
+                let pointer = format!("/{}/{}/{}", section, escape_pointer_token(path), method);
+                result.push((pointer, path_item, operation));
+            }
+        }
+    }
+    result
+}
+
+fn check_references(spec: &OpenApi, diagnostics: &mut Vec<Diagnostic>) {
+    let document = match serde_json::to_value(spec) {
+        Ok(document) => document,
+        Err(_) => return,
+    };
+    let mut references = Vec::new();
+    collect_references(&document, String::new(), Position::Object, &mut references);
+    for (pointer, reference) in references {
+        // Only local references can be checked.
+        let target = match reference.strip_prefix('#') {
+            Some(target) => percent_decode(target),
+            None => continue,
+        };
+        // `pointer()` takes care of the `~0` and `~1` escapes.
+        if document.pointer(&target).is_none() {
+            diagnostics.push(Diagnostic::new(
+                DiagnosticKind::DanglingRef,
+                pointer,
+                format!("The reference `{}` does not point to anything.", reference),
+            ));
+        }
+    }
+}
+
+/// The keys (outside of `components`) whose value is a map from a (user defined) name to an
+/// object. The names in these maps are never extensions or examples.
+const NAME_MAPS: &[&str] = &[
+    "webhooks",
+    "properties",
+    "patternProperties",
+    "definitions",
+    "$defs",
+    "dependentSchemas",
+    "headers",
+    "content",
+    "encoding",
+    "links",
+    "callbacks",
+    "variables",
+    "mapping",
+    "scopes",
+    "responses",
+];
+
+/// What kind of value is walked by [`collect_references`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Position {
+    /// An OpenAPI object or a schema.
+    Object,
+    /// A map from a name to an OpenAPI object or a schema.
+    Names,
+    /// A map from a name to an Example Object.
+    Examples,
+    /// An Example Object.
+    Example,
+}
+
+/// Collect all `$ref` values with the pointer to the `$ref`.
+/// The values of examples, defaults and extensions are skipped, as they can contain any value.
+fn collect_references(
+    value: &Value,
+    pointer: String,
+    position: Position,
+    references: &mut Vec<(String, String)>,
+) {
+    match value {
+        Value::Object(map) => {
+            for (key, value) in map {
+                let child = format!("{}/{}", pointer, escape_pointer_token(key));
+                let child_position = match (position, key.as_str(), value) {
+                    (Position::Names, ..) => Position::Object,
+                    (Position::Examples, ..) => Position::Example,
+                    (_, "$ref", Value::String(reference)) => {
+                        references.push((child, reference.clone()));
+                        continue;
+                    }
+                    // Extensions, and the values of examples, defaults, enums and constants.
+                    (_, key, _) if key.starts_with("x-") => continue,
+                    (_, "example" | "default" | "enum" | "const", _) => continue,
+                    (Position::Example, "value", _) => continue,
+                    // A map of Example Objects, or a JSON Schema `examples` list.
+                    (_, "examples", Value::Object(_)) => Position::Examples,
+                    (_, "examples", _) => continue,
+                    _ if pointer == "/components" || NAME_MAPS.contains(&key.as_str()) => {
+                        Position::Names
+                    }
+                    _ => Position::Object,
+                };
+                collect_references(value, child, child_position, references);
+            }
+        }
+        Value::Array(list) => {
+            for (index, value) in list.iter().enumerate() {
+                let child = format!("{}/{}", pointer, index);
+                collect_references(value, child, Position::Object, references);
+            }
+        }
+        _ => {}
+    }
+}
+
+/// Decode the `%xx` escapes in the fragment of a URI.
+fn percent_decode(fragment: &str) -> String {
+    let bytes = fragment.as_bytes();
+    let mut decoded = Vec::with_capacity(bytes.len());
+    let mut i = 0;
+    while i < bytes.len() {
+        let escaped = bytes
+            .get(i + 1..i + 3)
+            .filter(|_| bytes[i] == b'%')
+            .and_then(|hex| std::str::from_utf8(hex).ok())
+            .and_then(|hex| u8::from_str_radix(hex, 16).ok());
+        match escaped {
+            Some(byte) => {
+                decoded.push(byte);
+                i += 3;
+            }
+            None => {
+                decoded.push(bytes[i]);
+                i += 1;
+            }
+        }
+    }
+    String::from_utf8_lossy(&decoded).into_owned()
+}
+
+fn check_operation_ids(spec: &OpenApi, diagnostics: &mut Vec<Diagnostic>) {
+    let mut found: Map<String, String> = Map::new();
+    for (pointer, _, operation) in operations(spec) {
+        let operation_id = match &operation.operation_id {
+            Some(operation_id) => operation_id,
+            None => continue,
+        };
+        match found.get(operation_id) {
+            Some(first_pointer) => diagnostics.push(Diagnostic::new(
+                DiagnosticKind::DuplicateOperationId,
+                format!("{}/operationId", pointer),
+                format!(
+                    "The operationId `{}` is already used by the operation at `{}`.",
+                    operation_id, first_pointer
+                ),
+            )),
+            None => {
+                found.insert(operation_id.clone(), pointer);
+            }
+        }
+    }
+}
+
+fn check_path_parameters(spec: &OpenApi, diagnostics: &mut Vec<Diagnostic>) {
+    let component_parameters = spec.components.as_ref().map(|c| &c.parameters);
+    let resolve = |parameter: &'_ RefOr<Parameter>| -> Option<Parameter> {
+        match parameter {
+            RefOr::Object(parameter) => Some(parameter.clone()),
+            RefOr::Ref(reference) => {
+                let name = reference
+                    .reference
+                    .strip_prefix("#/components/parameters/")?;
+                match component_parameters?.get(name)? {
+                    RefOr::Object(parameter) => Some(parameter.clone()),
+                    RefOr::Ref(_) => None,
+                }
+            }
+        }
+    };
+    for (path, path_item) in &spec.paths {
+        let templated = templated_path_parameters(path);
+        if templated.is_empty() {
+            continue;
+        }
+        for (method, operation) in path_item.operations() {
+            let defined: Vec<Parameter> = path_item
+                .parameters
+                .iter()
+                .chain(&operation.parameters)
+                .filter_map(resolve)
+                .filter(|parameter| parameter.location == "path")
+                .collect();
+            for name in &templated {
+                if !defined.iter().any(|parameter| &parameter.name == name) {
+                    diagnostics.push(Diagnostic::new(
+                        DiagnosticKind::MissingPathParameter,
+                        format!("/paths/{}/{}", escape_pointer_token(path), method),
+                        format!(
+                            "The path parameter `{}` is not defined as `in: path` parameter.",
+                            name
+                        ),
+                    ));
+                }
+            }
+        }
+    }
+}
+
+/// Returns the names of all the `{name}` templates in the path.
+fn templated_path_parameters(path: &str) -> Vec<String> {
+    path.split('{')
+        .skip(1)
+        .filter_map(|part| part.split_once('}'))
+        .map(|(name, _)| name.to_owned())
+        .collect()
+}
+
+fn check_security_requirements(spec: &OpenApi, diagnostics: &mut Vec<Diagnostic>) {
+    let security_schemes = spec.components.as_ref().map(|c| &c.security_schemes);
+    let mut check = |pointer: String, requirements: &[SecurityRequirement]| {
+        for (index, requirement) in requirements.iter().enumerate() {
+            for name in requirement.keys() {
+                let defined = security_schemes
+                    .map(|schemes| schemes.contains_key(name))
+                    .unwrap_or(false);
+                if !defined {
+                    diagnostics.push(Diagnostic::new(
+                        DiagnosticKind::UndefinedSecurityScheme,
+                        format!("{}/{}/{}", pointer, index, escape_pointer_token(name)),
+                        format!(
+                            "The security scheme `{}` is not defined in the components.",
+                            name
+                        ),
+                    ));
+                }
+            }
+        }
+    };
+    check("/security".to_owned(), &spec.security);
+    for (pointer, _, operation) in operations(spec) {
+        if let Some(security) = &operation.security {
+            check(format!("{}/security", pointer), security);
+        }
+    }
+}
+
+fn check_response_descriptions(spec: &OpenApi, diagnostics: &mut Vec<Diagnostic>) {
+    let mut responses = Vec::new();
+    for (pointer, _, operation) in operations(spec) {
+        if let Some(response) = &operation.responses.default {
+            responses.push((format!("{}/responses/default", pointer), response));
+        }
+        for (status, response) in &operation.responses.responses {
+            responses.push((format!("{}/responses/{}", pointer, status), response));
+        }
+    }
+    if let Some(components) = &spec.components {
+        for (name, response) in &components.responses {
+            let pointer = format!("/components/responses/{}", escape_pointer_token(name));
+            responses.push((pointer, response));
+        }
+    }
+    for (pointer, response) in responses {
+        if let RefOr::Object(response) = response {
+            if response.description.trim().is_empty() {
+                diagnostics.push(Diagnostic::new(
+                    DiagnosticKind::EmptyResponseDescription,
+                    format!("{}/description", pointer),
+                    "The response has an empty description.".to_owned(),
+                ));
+            }
+        }
+    }
+}
@@ -0,0 +1,197 @@
+//! Contains the [`Visitor`] trait, used to walk over all the JSON Schemas and references
+//! (`$ref`) that are part of an [`OpenApi`] document.
+//!
+//! This works similar to [`schemars::visit`], but also visits all the parts of the OpenAPI
+//! document that can contain a JSON Schema or reference.
+
+use crate::openapi3::{
+    Callback, Components, Header, MediaType, OpenApi, Operation, Parameter, ParameterValue,
+    PathItem, RefOr, RequestBody, Response, Responses,
+};
+use schemars::schema::SchemaObject;
+
+/// Trait used to recursively modify all JSON Schemas and references in an [`OpenApi`] document.
+pub trait Visitor {
+    /// Override this method to modify a [`SchemaObject`] and (optionally) its subschemas.
+    ///
+    /// When overriding this method, you will usually want to call the [`visit_schema_object`]
+    /// function to visit the references and subschemas of the schema.
+    fn visit_schema_object(&mut self, schema: &mut SchemaObject) {
+        visit_schema_object(self, schema)
+    }
+
+    /// Override this method to inspect or modify a reference (the value of a `$ref`).
+    /// This is called for `RefOr::Ref` values, `PathItem` references and schema references.
+    fn visit_reference(&mut self, _reference: &mut String) {}
+}
+
+/// Visits all the paths, webhooks and components of the `OpenApi` document.
+pub fn visit_openapi<V: Visitor + ?Sized>(v: &mut V, spec: &mut OpenApi) {
+    for path_item in spec.paths.values_mut() {
+        visit_path_item(v, path_item);
+    }
+    for path_item in spec.webhooks.values_mut() {
+        visit_path_item(v, path_item);
+    }
+    if let Some(components) = &mut spec.components {
+        visit_components(v, components);
+    }
+}
+
+/// Visits all the components, including the ones that are a reference themselves.
+pub fn visit_components<V: Visitor + ?Sized>(v: &mut V, components: &mut Components) {
+    for schema in components.schemas.values_mut() {
+        v.visit_schema_object(schema);
+    }
+    for response in components.responses.values_mut() {
+        visit_ref_or(v, response, visit_response);
+    }
+    for parameter in components.parameters.values_mut() {
+        visit_ref_or(v, parameter, visit_parameter);
+    }
+    for example in components.examples.values_mut() {
+        visit_ref_or(v, example, |_, _| {});
+    }
+    for request_body in components.request_bodies.values_mut() {
+        visit_ref_or(v, request_body, visit_request_body);
+    }
+    for header in components.headers.values_mut() {
+        visit_ref_or(v, header, visit_header);
+    }
+    for security_scheme in components.security_schemes.values_mut() {
+        visit_ref_or(v, security_scheme, |_, _| {});
+    }
+    for link in components.links.values_mut() {
+        visit_ref_or(v, link, |_, _| {});
+    }
+    for callback in components.callbacks.values_mut() {
+        visit_ref_or(v, callback, visit_callback);
+    }
+}
+
+/// Visits the reference and all operations and parameters of the `PathItem`.
+pub fn visit_path_item<V: Visitor + ?Sized>(v: &mut V, path_item: &mut PathItem) {
+    if let Some(reference) = &mut path_item.reference {
+        v.visit_reference(reference);
+    }
+    for (_, operation) in path_item.operations_mut() {
+        visit_operation(v, operation);
+    }
+    for parameter in &mut path_item.parameters {
+        visit_ref_or(v, parameter, visit_parameter);
+    }
+}
+
+/// Visits the parameters, request body, responses and callbacks of the `Operation`.
+pub fn visit_operation<V: Visitor + ?Sized>(v: &mut V, operation: &mut Operation) {
+    for parameter in &mut operation.parameters {
+        visit_ref_or(v, parameter, visit_parameter);
+    }
+    if let Some(request_body) = &mut operation.request_body {
+        visit_ref_or(v, request_body, visit_request_body);
+    }
+    visit_responses(v, &mut operation.responses);
+    for callback in operation.callbacks.values_mut() {
+        visit_ref_or(v, callback, visit_callback);
+    }
+}
+
+/// Visits the `default` response and all other responses.
+pub fn visit_responses<V: Visitor + ?Sized>(v: &mut V, responses: &mut Responses) {
+    if let Some(response) = &mut responses.default {
+        visit_ref_or(v, response, visit_response);
+    }
+    for response in responses.responses.values_mut() {
+        visit_ref_or(v, response, visit_response);
+    }
+}
+
+/// Visits the headers, content and links of the `Response`.
+pub fn visit_response<V: Visitor + ?Sized>(v: &mut V, response: &mut Response) {
+    for header in response.headers.values_mut() {
+        visit_ref_or(v, header, visit_header);
+    }
+    for media_type in response.content.values_mut() {
+        visit_media_type(v, media_type);
+    }
+    for link in response.links.values_mut() {
+        visit_ref_or(v, link, |_, _| {});
+    }
+}
+
+/// Visits the schema or content of the `Parameter`.
+pub fn visit_parameter<V: Visitor + ?Sized>(v: &mut V, parameter: &mut Parameter) {
+    visit_parameter_value(v, &mut parameter.value);
+}
+
+/// Visits the schema or content of the `Header`.
+pub fn visit_header<V: Visitor + ?Sized>(v: &mut V, header: &mut Header) {
+    visit_parameter_value(v, &mut header.value);
+}
+
+/// Visits the content of the `RequestBody`.
+pub fn visit_request_body<V: Visitor + ?Sized>(v: &mut V, request_body: &mut RequestBody) {
+    for media_type in request_body.content.values_mut() {
+        visit_media_type(v, media_type);
+    }
+}
+
+/// Visits the schema and the encoding headers of the `MediaType`.
+pub fn visit_media_type<V: Visitor + ?Sized>(v: &mut V, media_type: &mut MediaType) {
+    if let Some(schema) = &mut media_type.schema {
+        v.visit_schema_object(schema);
+    }
+    for encoding in media_type.encoding.values_mut() {
+        for header in encoding.headers.values_mut() {
+            visit_ref_or(v, header, visit_header);
+        }
+    }
+}
+
+/// Visits all the `PathItem`s of the `Callback`.
+pub fn visit_callback<V: Visitor + ?Sized>(v: &mut V, callback: &mut Callback) {
+    for path_item in callback.callbacks.values_mut() {
+        visit_path_item(v, path_item);
+    }
+}
+
+/// Visits the reference of the `SchemaObject` and all its subschemas.
+///
+/// This should usually be called from [`Visitor::visit_schema_object`].
+pub fn visit_schema_object<V: Visitor + ?Sized>(v: &mut V, schema: &mut SchemaObject) {
+    if let Some(reference) = &mut schema.reference {
+        v.visit_reference(reference);
+    }
+    schemars::visit::visit_schema_object(&mut SchemaVisitor(v), schema);
+}
+
+fn visit_parameter_value<V: Visitor + ?Sized>(v: &mut V, value: &mut ParameterValue) {
+    match value {
+        ParameterValue::Schema { schema, .. } => v.visit_schema_object(schema),
+        ParameterValue::Content { content } => {
+            for media_type in content.values_mut() {
+                visit_media_type(v, media_type);
+            }
+        }
+    }
+}
+
+fn visit_ref_or<V: Visitor + ?Sized, T>(
+    v: &mut V,
+    ref_or: &mut RefOr<T>,
+    visit_object: impl FnOnce(&mut V, &mut T),
+) {
+    match ref_or {
+        RefOr::Ref(reference) => v.visit_reference(&mut reference.reference),
+        RefOr::Object(object) => visit_object(v, object),
+    }
+}
+
+/// Forwards the subschemas found by [`schemars::visit`] to the [`Visitor`].
+struct SchemaVisitor<'a, V: ?Sized>(&'a mut V);
+
+impl<V: Visitor + ?Sized> schemars::visit::Visitor for SchemaVisitor<'_, V> {
+    fn visit_schema_object(&mut self, schema: &mut SchemaObject) {
+        self.0.visit_schema_object(schema)
+    }
+}
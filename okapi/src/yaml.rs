@@ -0,0 +1,28 @@
+//! Read and write [`OpenApi`] documents as YAML.
+
+use crate::openapi3::OpenApi;
+
+impl OpenApi {
+    /// Serialize this document as YAML.
+    pub fn to_yaml(&self) -> Result<String, serde_yaml::Error> {
+        serde_yaml::to_string(self)
+    }
+
+    /// Deserialize a document from YAML.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use okapi::openapi3::OpenApi;
+    ///
+    /// let spec = OpenApi::from_yaml(
+    ///     "openapi: 3.0.0\ninfo:\n  title: Example\n  version: 1.0.0\npaths: {}\n",
+    /// )
+    /// .unwrap();
+    /// assert_eq!(spec.info.title, "Example");
+    /// assert_eq!(OpenApi::from_yaml(&spec.to_yaml().unwrap()).unwrap(), spec);
+    /// ```
+    pub fn from_yaml(yaml: &str) -> Result<Self, serde_yaml::Error> {
+        serde_yaml::from_str(yaml)
+    }
+}
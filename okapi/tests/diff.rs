@@ -0,0 +1,177 @@
+//! Tests for detecting (breaking) changes between 2 versions of a spec with `OpenApi::diff()`.
+
+use okapi::diff::ChangeKind;
+use okapi::openapi3::OpenApi;
+use serde_json::json;
+
+fn id_parameter() -> serde_json::Value {
+    json!({
+        "name": "id",
+        "in": "path",
+        "required": true,
+        "schema": { "type": "integer", "format": "uint64", "minimum": 0 }
+    })
+}
+
+fn ok_response() -> serde_json::Value {
+    json!({ "description": "" })
+}
+
+fn json_content(schema: &str) -> serde_json::Value {
+    json!({
+        "application/json": {
+            "schema": { "$ref": format!("#/components/schemas/{}", schema) }
+        }
+    })
+}
+
+fn v1() -> OpenApi {
+    serde_json::from_value(json!({
+        "openapi": "3.0.0",
+        "info": { "title": "Test", "version": "1" },
+        "paths": {
+            "/users/{id}": {
+                "get": {
+                    "parameters": [id_parameter()],
+                    "responses": {
+                        "200": { "description": "", "content": json_content("User") },
+                        "404": { "description": "The resource was not found." }
+                    }
+                },
+                "delete": {
+                    "parameters": [id_parameter()],
+                    "responses": { "200": ok_response() }
+                }
+            },
+            "/users/{id}/color": {
+                "post": {
+                    "parameters": [id_parameter()],
+                    "requestBody": { "content": json_content("Color"), "required": true },
+                    "responses": { "200": ok_response() }
+                }
+            }
+        },
+        "components": {
+            "schemas": {
+                "Color": { "type": "string", "enum": ["Red", "Green", "Blue"] },
+                "User": {
+                    "type": "object",
+                    "required": ["age", "id"],
+                    "properties": {
+                        "id": { "type": "integer" },
+                        "age": { "type": "integer" }
+                    }
+                }
+            }
+        }
+    }))
+    .unwrap()
+}
+
+fn v2() -> OpenApi {
+    serde_json::from_value(json!({
+        "openapi": "3.0.0",
+        "info": { "title": "Test", "version": "2" },
+        "paths": {
+            "/users/{id}": {
+                "get": {
+                    "parameters": [
+                        id_parameter(),
+                        {
+                            "name": "fields",
+                            "in": "query",
+                            "required": true,
+                            "schema": { "type": "string" }
+                        }
+                    ],
+                    "responses": {
+                        "200": { "description": "", "content": json_content("User") }
+                    }
+                }
+            },
+            "/users/{id}/color": {
+                "post": {
+                    "parameters": [id_parameter()],
+                    "requestBody": { "content": json_content("Color"), "required": true },
+                    "responses": { "200": ok_response() }
+                }
+            },
+            "/health": {
+                "get": { "responses": { "200": ok_response() } }
+            }
+        },
+        "components": {
+            "schemas": {
+                "Color": { "type": "string", "enum": ["Red", "Green"] },
+                "User": {
+                    "type": "object",
+                    "required": ["age", "id"],
+                    "properties": {
+                        "id": { "type": "integer" },
+                        "age": { "type": "string" }
+                    }
+                }
+            }
+        },
+        "security": [{ "ApiKey": [] }]
+    }))
+    .unwrap()
+}
+
+#[test]
+fn detect_breaking_changes() {
+    let changes: Vec<_> = v1()
+        .diff(&v2())
+        .into_iter()
+        .map(|change| (change.kind, change.breaking, change.pointer))
+        .collect();
+    let expected = [
+        (
+            ChangeKind::RequiredParameterAdded,
+            true,
+            "/paths/~1users~1{id}/get/parameters/1",
+        ),
+        (
+            ChangeKind::SchemaTypeChanged,
+            true,
+            "/paths/~1users~1{id}/get/responses/200/content/application~1json/schema/properties/age/type",
+        ),
+        (
+            ChangeKind::ResponseRemoved,
+            true,
+            "/paths/~1users~1{id}/get/responses/404",
+        ),
+        (
+            ChangeKind::SecurityChanged,
+            true,
+            "/paths/~1users~1{id}/get/security",
+        ),
+        (
+            ChangeKind::OperationRemoved,
+            true,
+            "/paths/~1users~1{id}/delete",
+        ),
+        (
+            ChangeKind::EnumValuesRemoved,
+            true,
+            "/paths/~1users~1{id}~1color/post/requestBody/content/application~1json/schema/enum",
+        ),
+        (
+            ChangeKind::SecurityChanged,
+            true,
+            "/paths/~1users~1{id}~1color/post/security",
+        ),
+        (ChangeKind::PathAdded, false, "/paths/~1health"),
+    ];
+    let expected: Vec<_> = expected
+        .into_iter()
+        .map(|(kind, breaking, pointer)| (kind, breaking, pointer.to_owned()))
+        .collect();
+    assert_eq!(changes, expected);
+}
+
+#[test]
+fn no_changes() {
+    let spec = v1();
+    assert!(spec.diff(&spec.clone()).is_empty());
+}
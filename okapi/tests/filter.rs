@@ -0,0 +1,117 @@
+//! Tests for filtering the operations of a spec with `OpenApi::retain_operations()`.
+
+use okapi::filter::{extension_equals, has_extension, has_tag, path_glob};
+use okapi::openapi3::OpenApi;
+use serde_json::json;
+
+fn spec() -> OpenApi {
+    serde_json::from_value(json!({
+        "openapi": "3.0.0",
+        "info": { "title": "Test", "version": "1" },
+        "paths": {
+            "/users": {
+                "get": { "tags": ["Users"], "responses": {} },
+                "parameters": [
+                    { "name": "page", "in": "query", "schema": { "type": "integer" } }
+                ]
+            },
+            "/internal/metrics": {
+                "get": {
+                    "tags": ["Internal"],
+                    "responses": {},
+                    "security": [{ "AdminKey": [] }],
+                    "x-internal": true,
+                    "x-audience": "ops"
+                }
+            },
+            "/shared": { "$ref": "#/components/pathItems/Shared" },
+            "/common": {
+                "parameters": [
+                    { "name": "locale", "in": "header", "schema": { "type": "string" } }
+                ]
+            }
+        },
+        "components": {
+            "securitySchemes": {
+                "AdminKey": { "type": "apiKey", "name": "x-admin-key", "in": "header" },
+                "ApiKey": { "type": "apiKey", "name": "x-api-key", "in": "header" }
+            }
+        },
+        "tags": [{ "name": "Users" }, { "name": "Internal" }, { "name": "Unused" }]
+    }))
+    .unwrap()
+}
+
+fn paths(spec: &OpenApi) -> Vec<&str> {
+    let mut paths: Vec<_> = spec.paths.keys().map(String::as_str).collect();
+    paths.sort_unstable();
+    paths
+}
+
+#[test]
+fn retain_operations_removes_unused_tags_and_security_schemes() {
+    let mut spec = spec();
+    spec.retain_operations(has_tag("Users"));
+
+    assert_eq!(paths(&spec), vec!["/common", "/shared", "/users"]);
+    // The other fields of a path item that still has operations are kept.
+    assert_eq!(spec.paths["/users"].parameters.len(), 1);
+    // Tags that were never used by an operation are kept.
+    let tags: Vec<_> = spec.tags.iter().map(|tag| tag.name.as_str()).collect();
+    assert_eq!(tags, vec!["Users", "Unused"]);
+    // Security schemes that were never used by an operation are kept.
+    let schemes: Vec<_> = spec
+        .components
+        .unwrap()
+        .security_schemes
+        .into_keys()
+        .collect();
+    assert_eq!(schemes, vec!["ApiKey"]);
+}
+
+#[test]
+fn retain_operations_keeps_path_items_without_operations() {
+    let mut spec = spec();
+    spec.retain_operations(|_, _, _| false);
+
+    // Only the path items that contained operations are removed.
+    assert_eq!(paths(&spec), vec!["/common", "/shared"]);
+    assert_eq!(
+        spec.paths["/shared"].reference.as_deref(),
+        Some("#/components/pathItems/Shared")
+    );
+}
+
+#[test]
+fn predicates() {
+    let spec = spec();
+    let operation = |path: &str| spec.paths[path].get.clone().unwrap();
+    let users = operation("/users");
+    let metrics = operation("/internal/metrics");
+
+    assert!(has_tag("Internal")("/internal/metrics", "get", &metrics));
+    assert!(!has_tag("Internal")("/users", "get", &users));
+    assert!(path_glob("/internal/*")(
+        "/internal/metrics",
+        "get",
+        &metrics
+    ));
+    assert!(!path_glob("/*")("/internal/metrics", "get", &metrics));
+    assert!(path_glob("/**")("/internal/metrics", "get", &metrics));
+    assert!(has_extension("x-internal")(
+        "/internal/metrics",
+        "get",
+        &metrics
+    ));
+    assert!(!has_extension("x-internal")("/users", "get", &users));
+    assert!(extension_equals("x-audience", json!("ops"))(
+        "/internal/metrics",
+        "get",
+        &metrics
+    ));
+    assert!(!extension_equals("x-audience", json!("dev"))(
+        "/internal/metrics",
+        "get",
+        &metrics
+    ));
+}
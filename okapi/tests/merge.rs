@@ -0,0 +1,292 @@
+//! Tests for merging specs with the different `MergeOptions`.
+
+use okapi::merge::{marge_spec_list_with_options, MergeOptions, OnConflict};
+use okapi::openapi3::OpenApi;
+use serde_json::json;
+
+/// A spec with a `GET /` operation that returns the `Error` schema, which has a single
+/// `property`.
+fn error_spec(operation_id: &str, property: &str) -> OpenApi {
+    serde_json::from_value(json!({
+        "openapi": "3.0.0",
+        "info": { "title": "Test", "version": "1" },
+        "paths": {
+            "/": {
+                "get": {
+                    "operationId": operation_id,
+                    "responses": {
+                        "200": {
+                            "description": "OK",
+                            "content": {
+                                "application/json": {
+                                    "schema": { "$ref": "#/components/schemas/Error" }
+                                }
+                            }
+                        }
+                    }
+                }
+            }
+        },
+        "components": {
+            "schemas": {
+                "Error": {
+                    "type": "object",
+                    "properties": { property: { "type": "string" } }
+                }
+            }
+        }
+    }))
+    .unwrap()
+}
+
+fn spec_list() -> Vec<(&'static str, OpenApi)> {
+    vec![
+        ("/post", error_spec("get_post", "post_id")),
+        ("/message", error_spec("get_message", "message")),
+    ]
+}
+
+fn error_properties(spec: &OpenApi, name: &str) -> Vec<String> {
+    let schema = &spec.components.as_ref().unwrap().schemas[name];
+    let properties = &schema.object.as_ref().unwrap().properties;
+    properties.keys().cloned().collect()
+}
+
+#[test]
+fn keep_first_by_default() {
+    let spec = marge_spec_list_with_options(&spec_list(), &MergeOptions::default()).unwrap();
+    assert_eq!(error_properties(&spec, "Error"), vec!["post_id"]);
+}
+
+#[test]
+fn keep_last() {
+    let options = MergeOptions {
+        on_conflict: OnConflict::KeepLast,
+    };
+    let spec = marge_spec_list_with_options(&spec_list(), &options).unwrap();
+    assert_eq!(error_properties(&spec, "Error"), vec!["message"]);
+}
+
+#[test]
+fn error_lists_all_conflicts() {
+    let options = MergeOptions {
+        on_conflict: OnConflict::Error,
+    };
+    let mut specs = spec_list();
+    // Document the same route twice.
+    specs.push(("/post", error_spec("get_message", "message")));
+    let err = marge_spec_list_with_options(&specs, &options).unwrap_err();
+
+    let conflicts: Vec<_> = err
+        .conflicts
+        .iter()
+        .map(|c| {
+            (
+                c.section.as_str(),
+                c.key.as_str(),
+                c.first_source.as_deref(),
+                c.second_source.as_str(),
+            )
+        })
+        .collect();
+    assert_eq!(
+        conflicts,
+        vec![
+            ("schemas", "Error", Some("/post"), "/message"),
+            ("operations", "get /post/", Some("/post"), "/post"),
+            ("schemas", "Error", Some("/post"), "/post"),
+        ]
+    );
+}
+
+/// A spec with a `GET {path}` operation that returns the `Page` schema, which references the
+/// `Error` schema with a single `property`.
+fn page_spec(path: &str, property: &str) -> OpenApi {
+    serde_json::from_value(json!({
+        "openapi": "3.0.0",
+        "info": { "title": "Test", "version": "1" },
+        "paths": {
+            path: {
+                "get": {
+                    "responses": {
+                        "200": {
+                            "description": "OK",
+                            "content": {
+                                "application/json": {
+                                    "schema": { "$ref": "#/components/schemas/Page" }
+                                }
+                            }
+                        }
+                    }
+                }
+            }
+        },
+        "components": {
+            "schemas": {
+                "Error": {
+                    "type": "object",
+                    "properties": { property: { "type": "string" } }
+                },
+                "Page": {
+                    "type": "object",
+                    "properties": { "error": { "$ref": "#/components/schemas/Error" } }
+                }
+            }
+        }
+    }))
+    .unwrap()
+}
+
+fn response_reference(spec: &OpenApi, path: &str) -> String {
+    let operation = spec.paths[path].get.as_ref().unwrap();
+    let response = match &operation.responses.responses["200"] {
+        okapi::openapi3::RefOr::Object(response) => response,
+        okapi::openapi3::RefOr::Ref(_) => panic!("Response should not be a ref."),
+    };
+    let schema = response.content["application/json"].schema.as_ref();
+    schema.unwrap().reference.clone().unwrap()
+}
+
+fn property_reference(spec: &OpenApi, schema: &str, property: &str) -> String {
+    let schema = &spec.components.as_ref().unwrap().schemas[schema];
+    match &schema.object.as_ref().unwrap().properties[property] {
+        okapi::schemars::schema::Schema::Object(property) => property.reference.clone().unwrap(),
+        okapi::schemars::schema::Schema::Bool(_) => panic!("Property should be an object."),
+    }
+}
+
+#[test]
+fn rename_conflicting_components() {
+    let options = MergeOptions {
+        on_conflict: OnConflict::Rename,
+    };
+    let spec = marge_spec_list_with_options(&spec_list(), &options).unwrap();
+
+    assert_eq!(error_properties(&spec, "Error"), vec!["post_id"]);
+    assert_eq!(error_properties(&spec, "message_Error"), vec!["message"]);
+    assert_eq!(
+        response_reference(&spec, "/post/"),
+        "#/components/schemas/Error"
+    );
+    assert_eq!(
+        response_reference(&spec, "/message/"),
+        "#/components/schemas/message_Error"
+    );
+}
+
+#[test]
+fn rename_components_that_reference_renamed_components() {
+    let options = MergeOptions {
+        on_conflict: OnConflict::Rename,
+    };
+    let specs = vec![
+        ("/post", page_spec("/", "post_id")),
+        ("/message", page_spec("/", "message")),
+    ];
+    let spec = marge_spec_list_with_options(&specs, &options).unwrap();
+
+    // Both `Page` schemas are the same, but reference a different `Error` schema.
+    let schemas = &spec.components.as_ref().unwrap().schemas;
+    let mut names: Vec<_> = schemas.keys().map(String::as_str).collect();
+    names.sort_unstable();
+    assert_eq!(
+        names,
+        vec!["Error", "Page", "message_Error", "message_Page"]
+    );
+    assert_eq!(
+        property_reference(&spec, "Page", "error"),
+        "#/components/schemas/Error"
+    );
+    assert_eq!(
+        property_reference(&spec, "message_Page", "error"),
+        "#/components/schemas/message_Error"
+    );
+    assert_eq!(
+        response_reference(&spec, "/post/"),
+        "#/components/schemas/Page"
+    );
+    assert_eq!(
+        response_reference(&spec, "/message/"),
+        "#/components/schemas/message_Page"
+    );
+}
+
+#[test]
+fn renamed_components_are_reused_when_merged_again() {
+    let options = MergeOptions {
+        on_conflict: OnConflict::Rename,
+    };
+    let specs = vec![
+        ("/post", page_spec("/", "post_id")),
+        ("/message", page_spec("/", "message")),
+        ("/message", page_spec("/latest", "message")),
+    ];
+    let spec = marge_spec_list_with_options(&specs, &options).unwrap();
+
+    let schemas = &spec.components.as_ref().unwrap().schemas;
+    let mut names: Vec<_> = schemas.keys().map(String::as_str).collect();
+    names.sort_unstable();
+    assert_eq!(
+        names,
+        vec!["Error", "Page", "message_Error", "message_Page"]
+    );
+    assert_eq!(
+        response_reference(&spec, "/message/latest"),
+        "#/components/schemas/message_Page"
+    );
+}
+
+#[test]
+fn extension_conflicts_name_the_spec_that_defined_them_first() {
+    fn spec(extension: Option<&str>) -> OpenApi {
+        let mut spec = json!({
+            "openapi": "3.0.0",
+            "info": { "title": "Test", "version": "1" },
+            "paths": { "/": { "get": { "responses": {} } } },
+            "components": {}
+        });
+        if let Some(value) = extension {
+            spec["x-owner"] = json!(value);
+            spec["paths"]["/"]["x-owner"] = json!(value);
+            spec["components"]["x-owner"] = json!(value);
+        }
+        serde_json::from_value(spec).unwrap()
+    }
+
+    let options = MergeOptions {
+        on_conflict: OnConflict::Error,
+    };
+    // The paths of `/users` and `/users/` are both `/users/`.
+    let specs = vec![
+        ("/users", spec(Some("alice"))),
+        ("/posts", spec(None)),
+        ("/users/", spec(Some("carol"))),
+    ];
+    let err = marge_spec_list_with_options(&specs, &options).unwrap_err();
+
+    let conflicts: Vec<_> = err
+        .conflicts
+        .iter()
+        .map(|conflict| {
+            (
+                conflict.section.as_str(),
+                conflict.key.as_str(),
+                conflict.first_source.as_deref(),
+                conflict.second_source.as_str(),
+            )
+        })
+        .collect();
+    assert_eq!(
+        conflicts,
+        vec![
+            (
+                "path_extensions",
+                "x-owner /users/",
+                Some("/users"),
+                "/users/"
+            ),
+            ("component_extensions", "x-owner", Some("/users"), "/users/"),
+            ("extensions", "x-owner", Some("/users"), "/users/"),
+        ]
+    );
+}
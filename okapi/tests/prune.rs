@@ -0,0 +1,95 @@
+//! Tests for removing unused components with `OpenApi::prune_unused_components()`.
+
+use okapi::openapi3::OpenApi;
+use serde_json::json;
+
+fn component_names(spec: &OpenApi) -> Vec<String> {
+    let components = spec.components.as_ref().unwrap();
+    let mut names: Vec<_> = components
+        .schemas
+        .keys()
+        .map(|name| format!("schemas/{}", name))
+        .chain(
+            components
+                .responses
+                .keys()
+                .map(|name| format!("responses/{}", name)),
+        )
+        .chain(
+            components
+                .security_schemes
+                .keys()
+                .map(|name| format!("securitySchemes/{}", name)),
+        )
+        .collect();
+    names.sort();
+    names
+}
+
+#[test]
+fn prune_unused_components() {
+    let mut spec: OpenApi = serde_json::from_value(json!({
+        "openapi": "3.0.0",
+        "info": { "title": "Test", "version": "1" },
+        "paths": {
+            "/user": {
+                "get": {
+                    "responses": {
+                        "200": {
+                            "description": "OK",
+                            "content": {
+                                "application/json": {
+                                    "schema": { "$ref": "#/components/schemas/User" }
+                                }
+                            }
+                        },
+                        "404": { "$ref": "#/components/responses/NotFound" }
+                    }
+                }
+            }
+        },
+        "components": {
+            "schemas": {
+                "User": {
+                    "type": "object",
+                    "properties": { "address": { "$ref": "#/components/schemas/Address" } }
+                },
+                "Address": { "type": "object" },
+                "Unused": {
+                    "type": "object",
+                    "properties": { "address": { "$ref": "#/components/schemas/Address" } }
+                },
+                "Error": { "type": "object" }
+            },
+            "responses": {
+                "NotFound": {
+                    "description": "Not found",
+                    "content": {
+                        "application/json": {
+                            "schema": { "$ref": "#/components/schemas/Error" }
+                        }
+                    }
+                },
+                "Unauthorized": { "description": "Unauthorized" }
+            },
+            "securitySchemes": {
+                "api_key": { "type": "apiKey", "name": "key", "in": "header" }
+            }
+        }
+    }))
+    .unwrap();
+
+    spec.prune_unused_components();
+    // `Address` is used by `User`, and `Error` by the `NotFound` response. Security schemes
+    // are always kept.
+    assert_eq!(
+        component_names(&spec),
+        vec![
+            "responses/NotFound",
+            "schemas/Address",
+            "schemas/Error",
+            "schemas/User",
+            "securitySchemes/api_key",
+        ]
+    );
+}
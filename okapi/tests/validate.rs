@@ -0,0 +1,353 @@
+//! Tests for the problems reported by `OpenApi::validate()`.
+
+use okapi::openapi3::OpenApi;
+use okapi::validate::{DiagnosticKind, Severity};
+use serde_json::json;
+
+fn spec(value: serde_json::Value) -> OpenApi {
+    serde_json::from_value(value).unwrap()
+}
+
+/// Returns the kind and pointer of every diagnostic, sorted by pointer.
+fn diagnostics(spec: &OpenApi) -> Vec<(DiagnosticKind, String)> {
+    let mut diagnostics: Vec<_> = spec
+        .validate()
+        .into_iter()
+        .map(|diagnostic| (diagnostic.kind, diagnostic.pointer))
+        .collect();
+    diagnostics.sort_by(|a, b| a.1.cmp(&b.1));
+    diagnostics
+}
+
+fn response(schema: serde_json::Value) -> serde_json::Value {
+    json!({
+        "description": "OK",
+        "content": { "application/json": { "schema": schema } }
+    })
+}
+
+#[test]
+fn valid_spec_has_no_diagnostics() {
+    let spec = spec(json!({
+        "openapi": "3.0.0",
+        "info": { "title": "Test", "version": "1" },
+        "paths": {
+            "/users/{id}": {
+                "get": {
+                    "operationId": "get_user",
+                    "parameters": [
+                        { "name": "id", "in": "path", "required": true, "schema": { "type": "integer" } }
+                    ],
+                    "responses": { "200": response(json!({ "$ref": "#/components/schemas/User" })) },
+                    "security": [{ "api_key": [] }]
+                }
+            }
+        },
+        "components": {
+            "schemas": { "User": { "type": "object" } },
+            "securitySchemes": { "api_key": { "type": "apiKey", "name": "key", "in": "header" } }
+        }
+    }));
+
+    assert_eq!(diagnostics(&spec), vec![]);
+}
+
+#[test]
+fn dangling_ref_is_reported() {
+    let spec = spec(json!({
+        "openapi": "3.0.0",
+        "info": { "title": "Test", "version": "1" },
+        "paths": {
+            "/users": {
+                "get": {
+                    "responses": { "200": response(json!({ "$ref": "#/components/schemas/User" })) }
+                }
+            }
+        },
+        "components": {
+            "schemas": {
+                "Group": {
+                    "type": "object",
+                    "properties": { "owner": { "$ref": "#/components/schemas/Owner" } }
+                }
+            }
+        }
+    }));
+
+    assert_eq!(
+        diagnostics(&spec),
+        vec![
+            (
+                DiagnosticKind::DanglingRef,
+                "/components/schemas/Group/properties/owner/$ref".to_owned()
+            ),
+            (
+                DiagnosticKind::DanglingRef,
+                "/paths/~1users/get/responses/200/content/application~1json/schema/$ref".to_owned()
+            ),
+        ]
+    );
+    assert!(spec
+        .validate()
+        .iter()
+        .all(|diagnostic| diagnostic.severity == Severity::Error));
+}
+
+#[test]
+fn escaped_refs_are_resolved() {
+    let spec = spec(json!({
+        "openapi": "3.0.0",
+        "info": { "title": "Test", "version": "1" },
+        "paths": {},
+        "components": {
+            "schemas": {
+                "a/b": { "type": "string" },
+                "a~b": { "type": "string" },
+                "User Name": { "type": "string" },
+                "User": {
+                    "type": "object",
+                    "properties": {
+                        "slash": { "$ref": "#/components/schemas/a~1b" },
+                        "tilde": { "$ref": "#/components/schemas/a~0b" },
+                        "space": { "$ref": "#/components/schemas/User%20Name" },
+                        "missing": { "$ref": "#/components/schemas/User%20Names" }
+                    }
+                }
+            }
+        }
+    }));
+
+    assert_eq!(
+        diagnostics(&spec),
+        vec![(
+            DiagnosticKind::DanglingRef,
+            "/components/schemas/User/properties/missing/$ref".to_owned()
+        )]
+    );
+}
+
+#[test]
+fn refs_in_properties_named_like_examples_and_extensions_are_checked() {
+    let spec = spec(json!({
+        "openapi": "3.0.0",
+        "info": { "title": "Test", "version": "1" },
+        "paths": {},
+        "components": {
+            "schemas": {
+                "User": {
+                    "type": "object",
+                    "properties": {
+                        "example": { "$ref": "#/components/schemas/Missing" },
+                        "examples": { "$ref": "#/components/schemas/Missing" },
+                        "x-name": { "$ref": "#/components/schemas/Missing" }
+                    },
+                    "example": { "$ref": "#/not/checked" }
+                },
+                "x-user": { "$ref": "#/components/schemas/Missing" }
+            }
+        }
+    }));
+
+    let pointers: Vec<_> = diagnostics(&spec)
+        .into_iter()
+        .map(|(kind, pointer)| {
+            assert_eq!(kind, DiagnosticKind::DanglingRef);
+            pointer
+        })
+        .collect();
+    assert_eq!(
+        pointers,
+        vec![
+            "/components/schemas/User/properties/example/$ref",
+            "/components/schemas/User/properties/examples/$ref",
+            "/components/schemas/User/properties/x-name/$ref",
+            "/components/schemas/x-user/$ref",
+        ]
+    );
+}
+
+#[test]
+fn refs_to_examples_are_checked_but_example_values_are_not() {
+    let spec = spec(json!({
+        "openapi": "3.0.0",
+        "info": { "title": "Test", "version": "1" },
+        "paths": {
+            "/users": {
+                "get": {
+                    "responses": {
+                        "200": {
+                            "description": "OK",
+                            "content": {
+                                "application/json": {
+                                    "examples": {
+                                        "value": { "value": { "$ref": "#/not/checked" } }
+                                    }
+                                }
+                            }
+                        }
+                    },
+                    "x-internal": { "$ref": "#/not/checked" }
+                }
+            }
+        },
+        "components": {
+            "examples": {
+                "Alias": { "$ref": "#/components/examples/Missing" },
+                "User": { "value": { "$ref": "#/not/checked" } }
+            }
+        }
+    }));
+
+    assert_eq!(
+        diagnostics(&spec),
+        vec![(
+            DiagnosticKind::DanglingRef,
+            "/components/examples/Alias/$ref".to_owned()
+        )]
+    );
+}
+
+#[test]
+fn default_values_are_not_checked() {
+    let spec = spec(json!({
+        "openapi": "3.0.0",
+        "info": { "title": "Test", "version": "1" },
+        "paths": {
+            "/users": {
+                "get": {
+                    "parameters": [{
+                        "name": "filter",
+                        "in": "query",
+                        "schema": {
+                            "type": "object",
+                            "default": { "$ref": "#/not/checked" }
+                        }
+                    }],
+                    "responses": {
+                        "default": response(json!({ "$ref": "#/components/schemas/Missing" }))
+                    }
+                }
+            }
+        },
+        "components": {
+            "schemas": {
+                "Settings": {
+                    "type": "object",
+                    "properties": {
+                        "default": { "$ref": "#/components/schemas/Missing" }
+                    },
+                    "default": { "$ref": "#/not/checked" },
+                    "examples": [{ "$ref": "#/not/checked" }]
+                }
+            }
+        }
+    }));
+
+    assert_eq!(
+        diagnostics(&spec),
+        vec![
+            (
+                DiagnosticKind::DanglingRef,
+                "/components/schemas/Settings/properties/default/$ref".to_owned()
+            ),
+            (
+                DiagnosticKind::DanglingRef,
+                "/paths/~1users/get/responses/default/content/application~1json/schema/$ref"
+                    .to_owned()
+            ),
+        ]
+    );
+}
+
+#[test]
+fn duplicate_operation_id_is_reported() {
+    let spec = spec(json!({
+        "openapi": "3.0.0",
+        "info": { "title": "Test", "version": "1" },
+        "paths": {
+            "/users": {
+                "get": { "operationId": "users", "responses": {} },
+                "post": { "operationId": "users", "responses": {} }
+            },
+            "/groups": {
+                "get": { "operationId": "groups", "responses": {} }
+            }
+        }
+    }));
+
+    let diagnostics = spec.validate();
+    assert_eq!(diagnostics.len(), 1);
+    assert_eq!(diagnostics[0].kind, DiagnosticKind::DuplicateOperationId);
+    assert_eq!(diagnostics[0].severity, Severity::Error);
+    assert_eq!(diagnostics[0].pointer, "/paths/~1users/post/operationId");
+    assert!(diagnostics[0].msg.contains("/paths/~1users/get"));
+}
+
+#[test]
+fn undefined_security_scheme_is_reported() {
+    let spec = spec(json!({
+        "openapi": "3.0.0",
+        "info": { "title": "Test", "version": "1" },
+        "paths": {
+            "/users": {
+                "get": {
+                    "responses": {},
+                    "security": [{ "api_key": [] }, { "oauth": ["read"] }]
+                }
+            }
+        },
+        "components": {
+            "securitySchemes": { "api_key": { "type": "apiKey", "name": "key", "in": "header" } }
+        },
+        "security": [{ "basic": [] }]
+    }));
+
+    assert_eq!(
+        diagnostics(&spec),
+        vec![
+            (
+                DiagnosticKind::UndefinedSecurityScheme,
+                "/paths/~1users/get/security/1/oauth".to_owned()
+            ),
+            (
+                DiagnosticKind::UndefinedSecurityScheme,
+                "/security/0/basic".to_owned()
+            ),
+        ]
+    );
+}
+
+#[test]
+fn empty_response_description_is_a_warning() {
+    let spec = spec(json!({
+        "openapi": "3.0.0",
+        "info": { "title": "Test", "version": "1" },
+        "paths": {
+            "/users": {
+                "get": {
+                    "responses": {
+                        "200": { "description": "OK" },
+                        "404": { "description": " " }
+                    }
+                }
+            }
+        },
+        "components": {
+            "responses": { "Error": { "description": "" } }
+        }
+    }));
+
+    let diagnostics = spec.validate();
+    assert_eq!(diagnostics.len(), 2);
+    assert!(diagnostics.iter().all(|diagnostic| diagnostic.kind
+        == DiagnosticKind::EmptyResponseDescription
+        && diagnostic.severity == Severity::Warning));
+    assert_eq!(
+        diagnostics[0].pointer,
+        "/paths/~1users/get/responses/404/description"
+    );
+    assert_eq!(
+        diagnostics[1].pointer,
+        "/components/responses/Error/description"
+    );
+}
@@ -7,11 +7,14 @@
 //! - `openapi_routes![...]`: Returns a closure for generating routes.
 //! - `openapi_spec![...]`: Returns a closure for generating OpenApi objects.
 //! - `#[derive(OpenApiFromRequest)]`: Implement `OpenApiFromRequest` trait for a given struct.
+//! - `#[derive(OpenApiResponder)]`: Implement `OpenApiResponderInner` trait for a given
+//!   struct or enum that derives `rocket::Responder`.
 //!
 
 mod openapi_attr;
 mod openapi_spec;
 mod parse_routes;
+mod responder_derive;
 
 use proc_macro::TokenStream;
 use quote::quote;
@@ -31,6 +34,16 @@ use syn::Ident;
 ///     format!("Hello world number {}", number)
 /// }
 /// ```
+///
+/// Responses that are not described by the return type can be added (or overridden) with the
+/// `response` attribute, which can be used multiple times:
+/// ```rust,ignore
+/// #[openapi(response(status = 404, description = "User not found", body = "Json<ApiError>"))]
+/// #[get("/users/<id>")]
+/// fn get_user(id: u64) -> Option<Json<User>> {
+///     None
+/// }
+/// ```
 #[proc_macro_attribute]
 pub fn openapi(args: TokenStream, mut input: TokenStream) -> TokenStream {
     // We don't need to modify/replace the input TokenStream,
@@ -85,6 +98,25 @@ pub fn openapi_spec(input: TokenStream) -> TokenStream {
     .into()
 }
 
+/// Returns a `Vec<rocket_okapi::registry::OpenApiOperation>` with the operations of the given
+/// routes, which can be used by the `OpenApiFairing` to document them.
+///
+/// Example:
+/// ```rust,ignore
+/// let fairing = rocket_okapi::fairing::OpenApiFairing::new(
+///     rocket_okapi::openapi_operations![get_message, post_message],
+/// );
+/// ```
+#[proc_macro]
+pub fn openapi_operations(input: TokenStream) -> TokenStream {
+    let operations =
+        openapi_spec::create_openapi_operations(input).unwrap_or_else(|e| e.to_compile_error());
+    (quote! {
+        #operations
+    })
+    .into()
+}
+
 /// Derive marco for the `OpenApiFromRequest` trait.
 ///
 /// This derive trait is a very simple implementation for anything that does not
@@ -136,6 +168,61 @@ pub fn open_api_from_request_derive(input: TokenStream) -> TokenStream {
     gen.into()
 }
 
+/// Derive marco for the `OpenApiResponderInner` trait.
+///
+/// This can be used together with Rocket's `#[derive(Responder)]`, and uses the same
+/// `#[response(status = ..., content_type = "...")]` attributes. For an enum, every variant
+/// is documented as a separate response, and the doc comment of the variant is used as the
+/// description of the response. The first field of a struct or variant is the responder
+/// that is documented.
+///
+/// The other fields are documented as headers of the response, using the doc comment of the
+/// field as description. The name of the header has to be set using
+/// `#[openapi_header(name = "ETag")]`, or use `#[openapi_header(skip)]` to not document a header.
+/// Fields marked with Rocket's `#[response(ignore)]` are not documented, the other keys of the
+/// `#[response]` attribute of a field are left to `#[derive(Responder)]`.
+/// The type of the field has to implement `rocket_okapi::response::OpenApiHeader`, which
+/// provides the schema of the header. `Option<_>` fields are documented as not required.
+/// `ContentType` fields are not documented as header.
+///
+/// Use:
+/// ```rust,ignore
+/// use rocket::serde::json::Json;
+/// use rocket_okapi::OpenApiResponder;
+///
+/// #[derive(rocket::Responder, OpenApiResponder)]
+/// pub enum CreateUser {
+///     /// The user was created.
+///     #[response(status = 201)]
+///     Created(Json<User>),
+///     /// A user with this name already exists.
+///     #[response(status = 409, content_type = "json")]
+///     Conflict(Json<ApiError>),
+/// }
+///
+/// #[derive(rocket::Responder, OpenApiResponder)]
+/// #[response(status = 201)]
+/// pub struct Created {
+///     inner: Json<User>,
+///     /// The url of the created user.
+///     #[openapi_header(name = "Location")]
+///     location: rocket::http::Header<'static>,
+///     #[openapi_header(name = "ETag")]
+///     etag: rocket::http::Header<'static>,
+/// }
+/// ```
+#[proc_macro_derive(OpenApiResponder, attributes(response, openapi_header))]
+pub fn open_api_responder_derive(input: TokenStream) -> TokenStream {
+    responder_derive::derive(input)
+}
+
+fn get_operation_entry_name(route_fn_name: &Ident) -> Ident {
+    Ident::new(
+        &format!("okapi_operation_for_{}_", route_fn_name),
+        route_fn_name.span(),
+    )
+}
+
 fn get_add_operation_fn_name(route_fn_name: &Ident) -> Ident {
     Ident::new(
         &format!("okapi_add_operation_for_{}_", route_fn_name),
@@ -1,7 +1,7 @@
 use syn::{Attribute, Lit::Str, Meta::NameValue, MetaNameValue};
 
 pub fn get_title_and_desc_from_doc(attrs: &[Attribute]) -> (Option<String>, Option<String>) {
-    let doc = match get_doc(attrs) {
+    let doc = match get_doc(attrs).and_then(|doc| none_if_empty(split_parameters(&doc).0)) {
         None => return (None, None),
         Some(doc) => doc,
     };
@@ -21,6 +21,76 @@ pub fn get_title_and_desc_from_doc(attrs: &[Attribute]) -> (Option<String>, Opti
     }
 }
 
+/// Get the descriptions of the parameters from the `# Parameters` section of the documentation.
+/// Every parameter is documented as a list item, for example: ``- `id`: The id of the user.``
+pub fn get_param_descs_from_doc(attrs: &[Attribute]) -> Vec<(String, String)> {
+    let parameters = match get_doc(attrs).and_then(|doc| split_parameters(&doc).1) {
+        None => return Vec::new(),
+        Some(parameters) => parameters,
+    };
+
+    let mut result: Vec<(String, String)> = Vec::new();
+    for line in parameters.lines() {
+        let line = line.trim();
+        let item = line.strip_prefix("- ").or_else(|| line.strip_prefix("* "));
+        match (item, result.last_mut()) {
+            (Some(item), _) => {
+                let item = item.trim();
+                let (name, desc) = match item.strip_prefix('`') {
+                    Some(rest) => rest.split_once('`').unwrap_or((rest, "")),
+                    None => item.split_once(':').unwrap_or((item, "")),
+                };
+                let desc = desc.trim_start_matches(|c: char| c == ':' || c.is_whitespace());
+                result.push((name.trim().to_owned(), desc.to_owned()));
+            }
+            // Lines that are not a list item continue the description of the previous item.
+            (None, Some((_, desc))) if !line.is_empty() => {
+                if !desc.is_empty() {
+                    desc.push(' ');
+                }
+                desc.push_str(line);
+            }
+            (None, _) => {}
+        }
+    }
+    result.retain(|(name, desc)| !name.is_empty() && !desc.is_empty());
+    result
+}
+
+/// Split the `# Parameters` section from the documentation.
+/// Returns the documentation without the section, and the content of the section (if any).
+fn split_parameters(doc: &str) -> (String, Option<String>) {
+    let is_heading = |line: &str| line.starts_with('#');
+    let is_parameters_heading = |line: &str| {
+        is_heading(line)
+            && line
+                .trim_start_matches('#')
+                .trim()
+                .eq_ignore_ascii_case("parameters")
+    };
+
+    let lines = doc.lines().collect::<Vec<_>>();
+    let start = match lines.iter().position(|line| is_parameters_heading(line)) {
+        None => return (doc.to_owned(), None),
+        Some(start) => start,
+    };
+    let end = lines[start + 1..]
+        .iter()
+        .position(|line| is_heading(line))
+        .map_or(lines.len(), |end| start + 1 + end);
+
+    // Keep the paragraphs before and after the section separated.
+    let rest = lines[..start]
+        .iter()
+        .chain(&[""])
+        .chain(&lines[end..])
+        .copied()
+        .collect::<Vec<_>>()
+        .join("\n");
+    let parameters = lines[start + 1..end].join("\n");
+    (rest.trim().to_owned(), none_if_empty(parameters))
+}
+
 fn merge_description_lines(doc: &str) -> Option<String> {
     let desc = doc
         .trim()
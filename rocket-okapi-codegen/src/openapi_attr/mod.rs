@@ -1,7 +1,7 @@
-mod doc_attr;
-mod route_attr;
+pub(crate) mod doc_attr;
+pub(crate) mod route_attr;
 
-use crate::get_add_operation_fn_name;
+use crate::{get_add_operation_fn_name, get_operation_entry_name};
 use darling::FromMeta;
 use proc_macro::TokenStream;
 use proc_macro2::Span;
@@ -40,6 +40,193 @@ struct OpenApiAttribute {
 
     /// Mark this operation as deprecated in the documentation.
     pub deprecated: bool,
+
+    /// Add or override responses, for example:
+    /// `#[openapi(response(status = 404, description = "User not found", body = "ApiError"))]`
+    #[darling(multiple, rename = "response")]
+    pub responses: Vec<ResponseAttribute>,
+
+    /// Document a parameter, for example:
+    /// `#[openapi(param(name = "id", description = "The id of the user.", example = 42))]`
+    #[darling(multiple, rename = "param")]
+    pub params: Vec<ParamAttribute>,
+
+    /// Add an example to the request body, the function is called to create the example:
+    /// `#[openapi(request_example = "examples::new_user")]` or
+    /// `#[openapi(request_example(value = "examples::new_user", name = "New user"))]`
+    #[darling(multiple)]
+    pub request_example: Vec<RequestExampleAttribute>,
+
+    /// Add an example to a response, the function is called to create the example:
+    /// `#[openapi(response_example(status = 200, value = "examples::user"))]`
+    #[darling(multiple)]
+    pub response_example: Vec<ResponseExampleAttribute>,
+
+    /// Override the security requirements of the operation.
+    /// Use `#[openapi(security = "none")]` to mark the operation as public, or
+    /// `#[openapi(security(name = "oauth", scopes("read:users")))]` to add a requirement.
+    #[darling(multiple)]
+    pub security: Vec<SecurityAttribute>,
+
+    /// Add a vendor extension to the operation, for example:
+    /// `#[openapi(extension(name = "x-internal", value = true))]` or
+    /// `#[openapi(extension(name = "x-rate-limit", value_fn = "extensions::rate_limit"))]`
+    #[darling(multiple)]
+    pub extension: Vec<ExtensionAttribute>,
+
+    /// Link to external documentation of the operation, for example:
+    /// `#[openapi(external_docs(url = "https://example.com", description = "More info"))]`
+    pub external_docs: Option<ExternalDocsAttribute>,
+}
+
+/// A vendor extension documented with `#[openapi(extension(...))]`.
+#[derive(Debug, FromMeta)]
+struct ExtensionAttribute {
+    /// The name of the extension, this has to start with `x-`.
+    pub name: String,
+
+    /// A literal that is used as the (JSON) value of the extension.
+    #[darling(default)]
+    pub value: Option<syn::Lit>,
+
+    /// Path to a function that returns the (serializable) value of the extension.
+    #[darling(default)]
+    pub value_fn: Option<syn::Path>,
+}
+
+/// External documentation documented with `#[openapi(external_docs(...))]`.
+#[derive(Debug, FromMeta)]
+struct ExternalDocsAttribute {
+    /// The url of the external documentation.
+    pub url: String,
+
+    /// A description of the external documentation.
+    #[darling(default)]
+    pub description: Option<String>,
+}
+
+/// A security requirement documented with `#[openapi(security ...)]`.
+#[derive(Debug)]
+enum SecurityAttribute {
+    /// `security = "none"`: The operation does not require any security.
+    None,
+    /// `security(name = "...", scopes(...))`: The operation requires this security scheme.
+    Requirement(SecurityRequirementAttribute),
+}
+
+impl FromMeta for SecurityAttribute {
+    fn from_string(value: &str) -> darling::Result<Self> {
+        match value {
+            "none" => Ok(SecurityAttribute::None),
+            _ => Err(darling::Error::unknown_value(value)),
+        }
+    }
+
+    fn from_list(items: &[syn::NestedMeta]) -> darling::Result<Self> {
+        SecurityRequirementAttribute::from_list(items).map(SecurityAttribute::Requirement)
+    }
+}
+
+#[derive(Debug, FromMeta)]
+struct SecurityRequirementAttribute {
+    /// The name of the security scheme.
+    pub name: String,
+
+    /// The scopes that are required, for example: `scopes("read:users", "write:users")`.
+    #[darling(default)]
+    pub scopes: Scopes,
+}
+
+#[derive(Debug, Default)]
+struct Scopes(Vec<String>);
+
+impl FromMeta for Scopes {
+    fn from_list(items: &[syn::NestedMeta]) -> darling::Result<Self> {
+        items
+            .iter()
+            .map(|item| match item {
+                syn::NestedMeta::Lit(syn::Lit::Str(scope)) => Ok(scope.value()),
+                _ => Err(darling::Error::unexpected_type("non-string").with_span(item)),
+            })
+            .collect::<darling::Result<_>>()
+            .map(Scopes)
+    }
+}
+
+/// An example documented with `#[openapi(request_example(...))]`.
+#[derive(Debug, FromMeta)]
+struct ExampleAttribute {
+    /// Path to a function that returns the (serializable) example.
+    pub value: syn::Path,
+
+    /// The name of the example. Named examples are added to `examples` instead of `example`.
+    #[darling(default)]
+    pub name: Option<String>,
+}
+
+/// The request example, which can be just the path to the function or a list.
+#[derive(Debug)]
+struct RequestExampleAttribute(ExampleAttribute);
+
+impl FromMeta for RequestExampleAttribute {
+    fn from_string(value: &str) -> darling::Result<Self> {
+        Ok(RequestExampleAttribute(ExampleAttribute {
+            value: syn::Path::from_string(value)?,
+            name: None,
+        }))
+    }
+
+    fn from_list(items: &[syn::NestedMeta]) -> darling::Result<Self> {
+        ExampleAttribute::from_list(items).map(RequestExampleAttribute)
+    }
+}
+
+/// An example documented with `#[openapi(response_example(...))]`.
+#[derive(Debug, FromMeta)]
+struct ResponseExampleAttribute {
+    /// The status code of the response.
+    pub status: u16,
+
+    /// Path to a function that returns the (serializable) example.
+    pub value: syn::Path,
+
+    /// The name of the example. Named examples are added to `examples` instead of `example`.
+    #[darling(default)]
+    pub name: Option<String>,
+}
+
+/// A parameter documented with `#[openapi(param(...))]`.
+#[derive(Debug, FromMeta)]
+struct ParamAttribute {
+    /// The name of the parameter.
+    pub name: String,
+
+    /// The description of the parameter.
+    #[darling(default)]
+    pub description: Option<String>,
+
+    /// An example value of the parameter.
+    #[darling(default)]
+    pub example: Option<syn::Lit>,
+}
+
+/// A response documented with `#[openapi(response(...))]`.
+#[derive(Debug, FromMeta)]
+struct ResponseAttribute {
+    /// The status code of the response.
+    pub status: u16,
+
+    /// The description of the response.
+    #[darling(default)]
+    pub description: Option<String>,
+
+    /// The type of the response body, the schema of this type is used as content.
+    #[darling(default)]
+    pub body: Option<Type>,
+
+    /// The Content-Type of the response body, defaults to `application/json`.
+    #[darling(default)]
+    pub content_type: Option<String>,
 }
 
 pub fn parse(args: TokenStream, input: TokenStream) -> TokenStream {
@@ -53,16 +240,48 @@ pub fn parse(args: TokenStream, input: TokenStream) -> TokenStream {
         }
     };
 
+    let route = route_attr::parse_attrs(&input.attrs);
     if entry_attributes.skip {
-        return create_empty_route_operation_fn(input);
+        let mut output = create_empty_route_operation_fn(input.clone());
+        if let Ok(route) = &route {
+            output.extend(create_operation_entry(&input, route));
+        }
+        return output;
     }
 
-    match route_attr::parse_attrs(&input.attrs) {
-        Ok(route) => create_route_operation_fn(input, route, &entry_attributes),
+    match route {
+        Ok(route) => {
+            let entry = create_operation_entry(&input, &route);
+            let mut output = create_route_operation_fn(input, route, &entry_attributes);
+            output.extend(entry);
+            output
+        }
         Err(e) => e,
     }
 }
 
+/// Create the `OpenApiOperation` of the route, which links the route to its documentation.
+fn create_operation_entry(route_fn: &ItemFn, route: &route_attr::Route) -> TokenStream {
+    let entry_name = get_operation_entry_name(&route_fn.sig.ident);
+    let fn_name = get_add_operation_fn_name(&route_fn.sig.ident);
+    let handler = route_fn.sig.ident.unraw().to_string();
+    let method = Ident::new(&to_pascal_case_string(route.method), Span::call_site());
+    let uri = route.origin.to_string();
+    TokenStream::from(quote! {
+        #[doc(hidden)]
+        #[allow(non_upper_case_globals)]
+        pub const #entry_name: ::rocket_okapi::registry::OpenApiOperation =
+            ::rocket_okapi::registry::OpenApiOperation {
+                handler: #handler,
+                module_path: module_path!(),
+                method: ::rocket::http::Method::#method,
+                uri: #uri,
+                add_operation: #fn_name,
+            };
+        ::rocket_okapi::__register_operation!(#entry_name);
+    })
+}
+
 fn create_empty_route_operation_fn(route_fn: ItemFn) -> TokenStream {
     let fn_name = get_add_operation_fn_name(&route_fn.sig.ident);
     TokenStream::from(quote! {
@@ -233,6 +452,20 @@ fn create_route_operation_fn(
         })
     }
 
+    // -- Format --
+    // https://rocket.rs/v0.5/guide/requests/#format
+    // For methods that support a payload the format is matched against the `Content-Type` of the
+    // request, for all other methods it is matched against the `Accept` header.
+    let format = route
+        .media_type
+        .as_ref()
+        .map(|media_type| media_type.to_string());
+    let (request_format, response_format) = if route.method.supports_payload() {
+        (format, None)
+    } else {
+        (None, format)
+    };
+
     // -- Body Data --
     // https://rocket.rs/v0.5/guide/requests/#body-data
     let request_body = match &route.data_param {
@@ -245,12 +478,28 @@ fn create_route_operation_fn(
             };
             // Add parameter to list
             params_names_used.push(data_param.clone());
-            quote! {
-                Some(<#ty as ::rocket_okapi::request::OpenApiFromData>::request_body(gen)?.into())
+            match &request_format {
+                // Use the `format` of the route as Content-Type of the request body.
+                Some(format) => quote! {
+                    Some({
+                        let mut request_body = <#ty as ::rocket_okapi::request::OpenApiFromData>::request_body(gen)?;
+                        ::rocket_okapi::util::set_request_body_content_type(&mut request_body, #format)?;
+                        request_body.into()
+                    })
+                },
+                None => quote! {
+                    Some(<#ty as ::rocket_okapi::request::OpenApiFromData>::request_body(gen)?.into())
+                },
             }
         }
         None => quote! { None },
     };
+    let set_response_format = match &response_format {
+        Some(format) => quote! {
+            ::rocket_okapi::util::set_format_content_type(&mut responses, #format);
+        },
+        None => quote! {},
+    };
 
     // -- Request Guards --
     // https://rocket.rs/v0.5/guide/requests/#request-guards
@@ -313,105 +562,318 @@ fn create_route_operation_fn(
 
     let deprecated = entry_attributes.deprecated;
 
+    let response_overrides = entry_attributes
+        .responses
+        .iter()
+        .map(|response| {
+            let status = response.status;
+            let description = match &response.description {
+                Some(x) => quote!(Some(#x.to_owned())),
+                None => quote!(None),
+            };
+            let content_type = response
+                .content_type
+                .as_deref()
+                .unwrap_or("application/json");
+            let content = match &response.body {
+                Some(ty) => quote!(Some((#content_type, gen.json_schema::<#ty>()))),
+                None => quote!(None),
+            };
+            quote! {
+                ::rocket_okapi::util::override_response(&mut responses, #status, #description, #content)?;
+            }
+        })
+        .collect::<Vec<_>>();
+
+    // Parameter descriptions from the `# Parameters` section of the doc comment. These are not
+    // required to match a parameter, because they can also describe ignored arguments.
+    let mut param_docs = doc_attr::get_param_descs_from_doc(&route_fn.attrs)
+        .into_iter()
+        .map(|(name, desc)| {
+            quote! {
+                ::rocket_okapi::util::document_parameter(&mut parameters, #name, Some(#desc), None::<()>)?;
+            }
+        })
+        .collect::<Vec<_>>();
+    // Parameters from the `#[openapi(param(...))]` attributes, these override the doc comment.
+    param_docs.extend(entry_attributes.params.iter().map(|param| {
+        let name = &param.name;
+        let description = match &param.description {
+            Some(x) => quote!(Some(#x)),
+            None => quote!(None),
+        };
+        let example = match &param.example {
+            Some(x) => quote!(Some(#x)),
+            None => quote!(None::<()>),
+        };
+        quote! {
+            if !::rocket_okapi::util::document_parameter(&mut parameters, #name, #description, #example)? {
+                return Err(::rocket_okapi::OpenApiError::new(format!(
+                    "The parameter `{}` documented by `#[openapi(param(...))]` does not exist.",
+                    #name,
+                )));
+            }
+        }
+    }));
+
+    // -- Examples --
+    if route.data_param.is_none() && !entry_attributes.request_example.is_empty() {
+        return quote! {
+            compile_error!("`request_example` can only be used on routes with a data param.");
+        }
+        .into();
+    }
+    let request_examples = entry_attributes
+        .request_example
+        .iter()
+        .map(|RequestExampleAttribute(example)| {
+            let value = &example.value;
+            let name = match &example.name {
+                Some(x) => quote!(Some(#x)),
+                None => quote!(None),
+            };
+            quote! {
+                ::rocket_okapi::util::add_request_body_example(request_body.as_mut(), #name, #value())?;
+            }
+        })
+        .collect::<Vec<_>>();
+    let request_body_mut = if request_examples.is_empty() {
+        quote!()
+    } else {
+        quote!(mut)
+    };
+    let response_examples = entry_attributes
+        .response_example
+        .iter()
+        .map(|example| {
+            let status = example.status;
+            let value = &example.value;
+            let name = match &example.name {
+                Some(x) => quote!(Some(#x)),
+                None => quote!(None),
+            };
+            quote! {
+                ::rocket_okapi::util::add_response_example(&mut responses, #status, #name, #value())?;
+            }
+        })
+        .collect::<Vec<_>>();
+
+    // -- Security --
+    let public = entry_attributes
+        .security
+        .iter()
+        .any(|security| matches!(security, SecurityAttribute::None));
+    if public && entry_attributes.security.len() > 1 {
+        return quote! {
+            compile_error!("`security = \"none\"` can not be combined with other security requirements.");
+        }
+        .into();
+    }
+    let security_requirements = entry_attributes
+        .security
+        .iter()
+        .filter_map(|security| match security {
+            SecurityAttribute::None => None,
+            SecurityAttribute::Requirement(requirement) => Some(requirement),
+        })
+        .map(|requirement| {
+            let name = &requirement.name;
+            let scopes = &requirement.scopes.0;
+            quote! {
+                security_requirements.push({
+                    let mut requirement = ::rocket_okapi::okapi::openapi3::SecurityRequirement::new();
+                    requirement.insert(#name.to_owned(), vec![#(#scopes.to_owned()),*]);
+                    requirement
+                });
+            }
+        })
+        .collect::<Vec<_>>();
+    let security = if public {
+        // An empty list removes the global security requirements for this operation.
+        quote! { let security = Some(Vec::new()); }
+    } else {
+        quote! {
+            // Add the security requirements from the `#[openapi(security(...))]` attributes.
+            #(#security_requirements)*
+            // Add `security` section if list is not empty
+            let security = if security_requirements.is_empty() {
+                None
+            } else {
+                Some(security_requirements)
+            };
+        }
+    };
+
+    // -- Extensions --
+    let mut extensions = Vec::new();
+    for extension in &entry_attributes.extension {
+        let name = &extension.name;
+        if !name.starts_with("x-") {
+            return quote! {
+                compile_error!(concat!("The extension `", #name, "` has to start with `x-`."));
+            }
+            .into();
+        }
+        let value = match (&extension.value, &extension.value_fn) {
+            (Some(value), None) => quote!(#value),
+            (None, Some(value_fn)) => quote!(#value_fn()),
+            _ => {
+                return quote! {
+                    compile_error!(concat!(
+                        "The extension `", #name, "` needs either a `value` or a `value_fn`."
+                    ));
+                }
+                .into()
+            }
+        };
+        extensions.push(quote! {
+            ::rocket_okapi::util::add_extension(&mut extensions, #name, #value)?;
+        });
+    }
+    let extensions_mut = if extensions.is_empty() {
+        quote!()
+    } else {
+        quote!(mut)
+    };
+    let external_docs = match &entry_attributes.external_docs {
+        Some(external_docs) => {
+            let url = &external_docs.url;
+            let description = match &external_docs.description {
+                Some(x) => quote!(Some(#x.to_owned())),
+                None => quote!(None),
+            };
+            quote! {
+                Some(::rocket_okapi::okapi::openapi3::ExternalDocs {
+                    url: #url.to_owned(),
+                    description: #description,
+                    ..Default::default()
+                })
+            }
+        }
+        None => quote!(None),
+    };
+
     // In case the user has set a manual `operation_id` use that, otherwise use generated one.
     let operation_id = match &entry_attributes.operation_id {
         Some(operation_id) => quote! { #operation_id.into() },
-        // Use value set by the variable set from the function below.
-        None => quote! { operation_id },
+        // Create it from the path of the function, which is set by `openapi_spec!`.
+        None => quote! { gen.operation_id(&operation_id) },
     };
 
+    let handler = route_fn.sig.ident.unraw().to_string();
+
     TokenStream::from(quote! {
         #[doc(hidden)]
         pub fn #fn_name(
             gen: &mut ::rocket_okapi::gen::OpenApiGenerator,
             operation_id: String,
         ) -> ::rocket_okapi::Result<()> {
-            let mut responses = <#return_type as ::rocket_okapi::response::OpenApiResponder>::responses(gen)?;
-            // Add responses from Request Guards.
-            let request_guard_responses = vec![#(#request_guard_responses),*];
-            for request_guard_response in request_guard_responses {
-                ::rocket_okapi::okapi::merge::merge_responses(&mut responses, &request_guard_response)?;
-            }
-
-            let request_body = #request_body;
-            // Add the security scheme that are quired for all the routes.
-            let mut security_requirements = Vec::new();
-            let mut server_requirements = Vec::new();
-
-            // Combine all parameters from all sources
-            // Add all from `path_params` and `path_multi_param`
-            let mut parameters: Vec<::rocket_okapi::okapi::openapi3::RefOr<::rocket_okapi::okapi::openapi3::Parameter>> = vec![#(#params),*];
-            // Add all from `query_params` and `query_multi_params`
-            let parameters_nested_list: Vec<Vec<::rocket_okapi::okapi::openapi3::Parameter>> = vec![#(#params_nested_list),*];
-            for inner_list in parameters_nested_list{
-                for item in inner_list{
-                    // convert every item from `Parameter` to `RefOr<Parameter>``
-                    parameters.push(item.into());
+            fn add_operation(
+                gen: &mut ::rocket_okapi::gen::OpenApiGenerator,
+                operation_id: String,
+            ) -> ::rocket_okapi::Result<()> {
+                let mut responses = <#return_type as ::rocket_okapi::response::OpenApiResponder>::responses(gen)?;
+                // Use the `format` of the route as Content-Type of the responses.
+                #set_response_format
+                // Add responses from Request Guards.
+                let request_guard_responses = vec![#(#request_guard_responses),*];
+                for request_guard_response in request_guard_responses {
+                    ::rocket_okapi::okapi::merge::merge_responses(&mut responses, &request_guard_response)?;
                 }
-            }
-            // Body Data does not add any parameters
-
-            // Add all Request Guards
-            let request_guards_route: Vec<::rocket_okapi::request::RequestHeaderInput> = vec![#(#params_request_guards),*];
-            for request_guard_route in request_guards_route {
-                use ::rocket_okapi::request::RequestHeaderInput;
-                match request_guard_route {
-                    // Add Parameters
-                    RequestHeaderInput::Parameter(p) => {
-                       parameters.push(p.into());
-                    }
-                    // Add Security Schemes, different section.
-                    RequestHeaderInput::Security(name, schema, requirement) => {
-                        // Add/replace the security scheme (global).
-                        gen.add_security_scheme(name, schema);
-                        // Add the security scheme that are quired for all the route.
-                        security_requirements.push(requirement);
-                    }
-                    // Add Server to this request.
-                    RequestHeaderInput::Server(url, description, variables) => {
-                        server_requirements.push(::rocket_okapi::okapi::openapi3::Server{
-                            url,
-                            description,
-                            variables,
-                            ..Default::default()
-                        });
+                // Add responses from the `#[openapi(response(...))]` attributes.
+                #(#response_overrides)*
+                // Add the examples of the responses.
+                #(#response_examples)*
+
+                let #request_body_mut request_body = #request_body;
+                // Add the examples of the request body.
+                #(#request_examples)*
+                // Add the security scheme that are quired for all the routes.
+                let mut security_requirements = Vec::new();
+                let mut server_requirements = Vec::new();
+
+                // Combine all parameters from all sources
+                // Add all from `path_params` and `path_multi_param`
+                let mut parameters: Vec<::rocket_okapi::okapi::openapi3::RefOr<::rocket_okapi::okapi::openapi3::Parameter>> = vec![#(#params),*];
+                // Add all from `query_params` and `query_multi_params`
+                let parameters_nested_list: Vec<Vec<::rocket_okapi::okapi::openapi3::Parameter>> = vec![#(#params_nested_list),*];
+                for inner_list in parameters_nested_list{
+                    for item in inner_list{
+                        // convert every item from `Parameter` to `RefOr<Parameter>``
+                        parameters.push(item.into());
                     }
-                    _ => {
+                }
+                // Body Data does not add any parameters
+
+                // Add all Request Guards
+                let request_guards_route: Vec<::rocket_okapi::request::RequestHeaderInput> = vec![#(#params_request_guards),*];
+                for request_guard_route in request_guards_route {
+                    use ::rocket_okapi::request::RequestHeaderInput;
+                    match request_guard_route {
+                        // Add Parameters
+                        RequestHeaderInput::Parameter(p) => {
+                           parameters.push(p.into());
+                        }
+                        // Add Security Schemes, different section.
+                        RequestHeaderInput::Security(name, schema, requirement) => {
+                            // Add/replace the security scheme (global).
+                            gen.add_security_scheme(name, schema);
+                            // Add the security scheme that are quired for all the route.
+                            security_requirements.push(requirement);
+                        }
+                        // Add Server to this request.
+                        RequestHeaderInput::Server(url, description, variables) => {
+                            server_requirements.push(::rocket_okapi::okapi::openapi3::Server{
+                                url,
+                                description,
+                                variables,
+                                ..Default::default()
+                            });
+                        }
+                        _ => {
+                        }
                     }
                 }
+
+                // Add the descriptions and examples of the parameters.
+                #(#param_docs)*
+
+                #security
+                // Add `servers` section if list is not empty
+                let servers = if server_requirements.is_empty() {
+                    None
+                } else {
+                    Some(server_requirements)
+                };
+                // Add the vendor extensions.
+                let #extensions_mut extensions = ::rocket_okapi::okapi::openapi3::Object::new();
+                #(#extensions)*
+                // Add route/endpoint to OpenApi object.
+                gen.add_operation(::rocket_okapi::OperationInfo {
+                    path: #path.to_owned(),
+                    method: ::rocket::http::Method::#method,
+                    operation: ::rocket_okapi::okapi::openapi3::Operation {
+                        operation_id: Some(#operation_id),
+                        responses,
+                        request_body,
+                        parameters,
+                        summary: #title,
+                        description: #desc,
+                        security,
+                        servers,
+                        tags: vec![#(#tags),*],
+                        deprecated: #deprecated,
+                        external_docs: #external_docs,
+                        extensions,
+                        ..Default::default()
+                    },
+                })
             }
 
-            // Add `security` section if list is not empty
-            let security = if security_requirements.is_empty() {
-                None
-            } else {
-                Some(security_requirements)
-            };
-            // Add `servers` section if list is not empty
-            let servers = if server_requirements.is_empty() {
-                None
-            } else {
-                Some(server_requirements)
-            };
-            // Add route/endpoint to OpenApi object.
-            gen.add_operation(::rocket_okapi::OperationInfo {
-                path: #path.to_owned(),
-                method: ::rocket::http::Method::#method,
-                operation: ::rocket_okapi::okapi::openapi3::Operation {
-                    operation_id: Some(#operation_id),
-                    responses,
-                    request_body,
-                    parameters,
-                    summary: #title,
-                    description: #desc,
-                    security,
-                    servers,
-                    tags: vec![#(#tags),*],
-                    deprecated: #deprecated,
-                    ..Default::default()
-                },
-            });
-            Ok(())
+            // Add the route to all errors.
+            add_operation(gen, operation_id).map_err(|err| {
+                err.with_route(#handler, ::rocket::http::Method::#method, #path)
+            })
         }
     })
 }
@@ -10,7 +10,6 @@ use syn::{Attribute, Meta, MetaList, NestedMeta};
 pub struct Route {
     pub method: Method,
     pub origin: Origin<'static>,
-    #[allow(dead_code)]
     pub media_type: Option<MediaType>,
     pub data_param: Option<String>,
 }
@@ -76,7 +75,7 @@ impl Route {
 #[derive(Debug)]
 struct OriginMeta(Origin<'static>);
 #[derive(Debug)]
-struct MediaTypeMeta(MediaType);
+pub(crate) struct MediaTypeMeta(pub MediaType);
 #[derive(Debug)]
 struct MethodMeta(Method);
 
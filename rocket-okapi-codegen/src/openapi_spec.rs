@@ -1,4 +1,4 @@
-use crate::get_add_operation_fn_name;
+use crate::{get_add_operation_fn_name, get_operation_entry_name};
 use proc_macro::TokenStream;
 use proc_macro2::TokenStream as TokenStream2;
 use quote::quote;
@@ -42,17 +42,39 @@ pub fn create_openapi_spec(routes: TokenStream) -> Result<TokenStream2> {
     })
 }
 
+/// Parses routes and returns a `Vec` with the `OpenApiOperation` of every route.
+pub fn create_openapi_operations(routes: TokenStream) -> Result<TokenStream2> {
+    let paths = <Punctuated<Path, Comma>>::parse_terminated.parse(routes)?;
+    let entries = paths.into_iter().map(|mut path| {
+        let last_seg = path.segments.last_mut().expect("syn::Path has segments");
+        last_seg.ident = get_operation_entry_name(&last_seg.ident);
+        path
+    });
+    Ok(quote! {
+        vec![#(#entries),*]
+    })
+}
+
 fn create_add_operations(paths: Punctuated<Path, Comma>) -> TokenStream2 {
     let function_calls = paths.into_iter().map(|path| {
         let fn_name = fn_name_for_add_operation(path.clone());
         let operation_id = operation_id(&path);
         quote! {
-            #fn_name(&mut gen, #operation_id.to_owned())
-                .expect(&format!("Could not generate OpenAPI operation for `{}`.", stringify!(#path)));
+            if let Err(err) = #fn_name(&mut gen, #operation_id.to_owned()) {
+                errors.push(err);
+            }
         }
     });
+    // Collect the errors of all routes, so they can be reported at once.
     quote! {
+        let mut errors = Vec::new();
         #(#function_calls)*
+        if !errors.is_empty() {
+            panic!(
+                "Could not generate OpenAPI operations.\n{}",
+                ::rocket_okapi::OpenApiError::multiple(errors)
+            );
+        }
     }
 }
 
@@ -68,5 +90,5 @@ fn operation_id(fn_path: &Path) -> String {
         .iter()
         .map(|s| s.ident.to_string())
         .collect();
-    idents.join("_")
+    idents.join("::")
 }
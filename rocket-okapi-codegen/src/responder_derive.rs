@@ -0,0 +1,274 @@
+use crate::openapi_attr::doc_attr::get_title_and_desc_from_doc;
+use crate::openapi_attr::route_attr::MediaTypeMeta;
+use darling::ast::{Data, Fields};
+use darling::{FromDeriveInput, FromField, FromVariant};
+use proc_macro::TokenStream;
+use proc_macro2::TokenStream as TokenStream2;
+use quote::quote;
+use syn::{parse_quote, Attribute, DeriveInput, Generics, Ident, Meta, NestedMeta, Type};
+
+/// The `#[response(...)]` attribute on a struct, enum or enum variant.
+/// These are the same attributes as used by `#[derive(rocket::Responder)]`.
+#[derive(Debug, FromDeriveInput)]
+#[darling(
+    attributes(response),
+    forward_attrs(doc),
+    supports(struct_any, enum_any)
+)]
+struct ResponderInput {
+    ident: Ident,
+    generics: Generics,
+    attrs: Vec<Attribute>,
+    data: Data<ResponderVariant, ResponderField>,
+    #[darling(default)]
+    status: Option<u16>,
+    #[darling(default)]
+    content_type: Option<MediaTypeMeta>,
+}
+
+#[derive(Debug, FromVariant)]
+#[darling(attributes(response), forward_attrs(doc))]
+struct ResponderVariant {
+    ident: Ident,
+    attrs: Vec<Attribute>,
+    fields: Fields<ResponderField>,
+    #[darling(default)]
+    status: Option<u16>,
+    #[darling(default)]
+    content_type: Option<MediaTypeMeta>,
+}
+
+/// A field of a struct or enum variant, with the `#[openapi_header(name = "...", skip)]`
+/// attribute. Rocket's `#[response(...)]` attribute is forwarded, see [`is_ignored`].
+#[derive(Debug, FromField)]
+#[darling(attributes(openapi_header), forward_attrs(doc, response))]
+struct ResponderField {
+    ident: Option<Ident>,
+    ty: Type,
+    attrs: Vec<Attribute>,
+    /// The name of the header, required to document the header.
+    #[darling(default)]
+    name: Option<String>,
+    /// Do not document this header.
+    #[darling(default)]
+    skip: bool,
+}
+
+/// A single response, created from a struct or an enum variant.
+struct ResponseInfo<'a> {
+    ty: &'a Type,
+    status: Option<u16>,
+    content_type: Option<String>,
+    description: Option<String>,
+    headers: Vec<HeaderInfo<'a>>,
+}
+
+/// A header that is added to the response by a field of the responder.
+struct HeaderInfo<'a> {
+    ty: &'a Type,
+    name: String,
+    description: Option<String>,
+}
+
+pub fn derive(input: TokenStream) -> TokenStream {
+    let ast: DeriveInput = match syn::parse(input) {
+        Ok(ast) => ast,
+        Err(e) => return e.to_compile_error().into(),
+    };
+    match ResponderInput::from_derive_input(&ast) {
+        Ok(input) => match create_impl(&input) {
+            Ok(tokens) => tokens.into(),
+            Err(e) => e.write_errors().into(),
+        },
+        Err(e) => e.write_errors().into(),
+    }
+}
+
+fn create_impl(input: &ResponderInput) -> Result<TokenStream2, darling::Error> {
+    let default_content_type = input.content_type.as_ref().map(|c| c.0.to_string());
+    let responses = match &input.data {
+        Data::Struct(fields) => vec![ResponseInfo {
+            ty: first_field(fields, &input.ident)?,
+            status: input.status,
+            content_type: default_content_type,
+            description: get_description(&input.attrs),
+            headers: get_headers(fields)?,
+        }],
+        Data::Enum(variants) => variants
+            .iter()
+            .map(|variant| {
+                Ok(ResponseInfo {
+                    ty: first_field(&variant.fields, &variant.ident)?,
+                    status: variant.status.or(input.status),
+                    content_type: variant
+                        .content_type
+                        .as_ref()
+                        .map(|c| c.0.to_string())
+                        .or_else(|| default_content_type.clone()),
+                    description: get_description(&variant.attrs),
+                    headers: get_headers(&variant.fields)?,
+                })
+            })
+            .collect::<Result<Vec<_>, darling::Error>>()?,
+    };
+
+    let mut generics = input.generics.clone();
+    for response in &responses {
+        let ty = response.ty;
+        let predicates = &mut generics.make_where_clause().predicates;
+        predicates.push(parse_quote!(#ty: ::rocket_okapi::response::OpenApiResponderInner));
+        for header in &response.headers {
+            let ty = header.ty;
+            predicates.push(parse_quote!(#ty: ::rocket_okapi::response::OpenApiHeader));
+        }
+    }
+    let (impl_generics, ty_generics, where_clause) = generics.split_for_impl();
+    let name = &input.ident;
+    let add_responses = responses.iter().map(create_add_response);
+
+    Ok(quote! {
+        impl #impl_generics ::rocket_okapi::response::OpenApiResponderInner for #name #ty_generics
+            #where_clause
+        {
+            fn responses(
+                gen: &mut ::rocket_okapi::gen::OpenApiGenerator,
+            ) -> ::rocket_okapi::Result<::rocket_okapi::okapi::openapi3::Responses> {
+                let mut responses = ::rocket_okapi::okapi::openapi3::Responses::default();
+                #(#add_responses)*
+                Ok(responses)
+            }
+        }
+    })
+}
+
+fn create_add_response(response: &ResponseInfo) -> TokenStream2 {
+    let ty = response.ty;
+    let set_status = response.status.map(|status| {
+        quote! {
+            ::rocket_okapi::util::set_status_code(&mut new_responses, #status)?;
+        }
+    });
+    let set_content_type = response.content_type.as_ref().map(|content_type| {
+        quote! {
+            ::rocket_okapi::util::set_content_type(&mut new_responses, #content_type)?;
+        }
+    });
+    let set_description = response.description.as_ref().map(|description| {
+        quote! {
+            ::rocket_okapi::util::set_description(&mut new_responses, #description)?;
+        }
+    });
+    let add_headers = response.headers.iter().map(|header| {
+        let ty = header.ty;
+        let name = &header.name;
+        let description = match &header.description {
+            Some(description) => quote! { Some(#description.to_owned()) },
+            None => quote! { None },
+        };
+        quote! {
+            ::rocket_okapi::util::add_response_header(
+                &mut new_responses,
+                #name,
+                #description,
+                <#ty as ::rocket_okapi::response::OpenApiHeader>::header_schema(gen),
+                <#ty as ::rocket_okapi::response::OpenApiHeader>::header_required(),
+            )?;
+        }
+    });
+    quote! {
+        {
+            let mut new_responses =
+                <#ty as ::rocket_okapi::response::OpenApiResponderInner>::responses(gen)?;
+            #set_status
+            #set_content_type
+            #set_description
+            #(#add_headers)*
+            responses = ::rocket_okapi::util::produce_any_responses(responses, new_responses)?;
+        }
+    }
+}
+
+/// The first field is the responder, the other fields are headers.
+fn first_field<'a>(
+    fields: &'a Fields<ResponderField>,
+    ident: &Ident,
+) -> Result<&'a Type, darling::Error> {
+    fields.iter().next().map(|field| &field.ty).ok_or_else(|| {
+        darling::Error::custom("A responder needs at least one field.").with_span(ident)
+    })
+}
+
+/// All fields after the first one are headers, except the ones marked with `#[response(ignore)]`.
+/// `ContentType` fields are not documented as a header, as they set the Content-Type of the
+/// response content.
+/// The name of a header is only known at runtime, so it has to be set with
+/// `#[openapi_header(name = "...")]`.
+fn get_headers(fields: &Fields<ResponderField>) -> Result<Vec<HeaderInfo<'_>>, darling::Error> {
+    let mut errors = darling::Error::accumulator();
+    let headers = fields
+        .iter()
+        .skip(1)
+        .filter(|field| !field.skip && !is_ignored(&field.attrs) && !is_content_type(&field.ty))
+        .filter_map(|field| {
+            let name = match &field.name {
+                Some(name) => name.clone(),
+                None => {
+                    let error = darling::Error::custom(
+                        "The name of this header is unknown, add \
+                        `#[openapi_header(name = \"...\")]` to document it, \
+                        or `#[openapi_header(skip)]` to not document it.",
+                    );
+                    match &field.ident {
+                        Some(ident) => errors.push(error.with_span(ident)),
+                        None => errors.push(error.with_span(&field.ty)),
+                    }
+                    return None;
+                }
+            };
+            Some(HeaderInfo {
+                ty: &field.ty,
+                name,
+                description: get_description(&field.attrs),
+            })
+        })
+        .collect();
+    errors.finish()?;
+    Ok(headers)
+}
+
+/// Returns `true` if the field has Rocket's `#[response(ignore)]` attribute, so the field is
+/// not used as a header. The other keys of the attribute are left to `#[derive(Responder)]`,
+/// which also reports any errors in it.
+fn is_ignored(attrs: &[Attribute]) -> bool {
+    attrs
+        .iter()
+        .filter(|attr| attr.path.is_ident("response"))
+        .filter_map(|attr| match attr.parse_meta() {
+            Ok(Meta::List(list)) => Some(list.nested),
+            _ => None,
+        })
+        .flatten()
+        .any(|nested| matches!(nested, NestedMeta::Meta(Meta::Path(path)) if path.is_ident("ignore")))
+}
+
+fn is_content_type(ty: &Type) -> bool {
+    match ty {
+        Type::Path(path) => path
+            .path
+            .segments
+            .last()
+            .map(|segment| segment.ident == "ContentType")
+            .unwrap_or(false),
+        _ => false,
+    }
+}
+
+/// Use the doc comment as the description of the response. The title and the description are
+/// both used as plain text, separated like paragraphs.
+fn get_description(attrs: &[Attribute]) -> Option<String> {
+    match get_title_and_desc_from_doc(attrs) {
+        (Some(title), Some(desc)) => Some(format!("{}\n\n{}", title, desc)),
+        (title, None) => title,
+        (None, desc) => desc,
+    }
+}
@@ -1,4 +1,5 @@
 use okapi::merge::MergeError;
+use rocket::http::Method;
 use std::error::Error;
 use std::fmt;
 
@@ -6,29 +7,187 @@ use std::fmt;
 pub type Result<T> = std::result::Result<T, OpenApiError>;
 
 /// The error type returned by `rocket_okapi` when something fails.
+///
+/// The [`kind`](OpenApiError::kind) describes what went wrong. Errors that happen while
+/// generating the documentation of a route also contain the [`route`](OpenApiError::route).
 #[derive(Debug, Clone)]
 pub struct OpenApiError {
-    msg: String,
+    // Boxed, to keep `Result`s small.
+    kind: Box<OpenApiErrorKind>,
+    route: Option<Box<RouteContext>>,
+}
+
+/// The different kinds of [`OpenApiError`]s.
+#[derive(Debug, Clone)]
+#[non_exhaustive]
+pub enum OpenApiErrorKind {
+    /// A `$ref` object had to be altered, which is not supported.
+    /// For example setting the status code of a response that is a reference.
+    RefAlteration(String),
+    /// The status code of a response conflicts with the documented responses.
+    StatusConflict(String),
+    /// The Content-Type of a route conflicts with the documented request body.
+    ContentTypeConflict(String),
+    /// A value could not be converted to JSON, for example an example or extension.
+    SchemaFailure(String),
+    /// Merging two OpenAPI specs failed.
+    MergeConflict(MergeError),
+    /// Multiple routes are documented with the same path and method.
+    RouteConflict {
+        /// The method of the routes.
+        method: Method,
+        /// The path of the routes.
+        path: String,
+    },
+    /// Multiple operations use the same `operationId`.
+    DuplicateOperationId(DuplicateOperationId),
+    /// Multiple errors happened, for example when generating the documentation of multiple
+    /// routes failed.
+    Multiple(Vec<OpenApiError>),
+    /// Any other error.
+    Other(String),
+}
+
+/// The route for which generating the documentation failed.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RouteContext {
+    /// The name of the route function.
+    pub handler: String,
+    /// The method of the route.
+    pub method: Method,
+    /// The (OpenAPI) path of the route, for example `/users/{id}`.
+    pub path: String,
 }
 
 impl OpenApiError {
     /// Create a new `OpenApiError` containing a message.
     #[must_use]
     pub fn new(msg: String) -> Self {
-        OpenApiError { msg }
+        Self::from_kind(OpenApiErrorKind::Other(msg))
+    }
+
+    /// Create a new `OpenApiError` of the given kind.
+    #[must_use]
+    pub fn from_kind(kind: OpenApiErrorKind) -> Self {
+        OpenApiError {
+            kind: Box::new(kind),
+            route: None,
+        }
+    }
+
+    /// Combine multiple errors into one error.
+    /// If there is only one error, that error is returned.
+    #[must_use]
+    pub fn multiple(mut errors: Vec<OpenApiError>) -> Self {
+        if errors.len() == 1 {
+            errors.remove(0)
+        } else {
+            Self::from_kind(OpenApiErrorKind::Multiple(errors))
+        }
+    }
+
+    /// Add the route that caused this error. If the error already has a route, it is kept.
+    #[must_use]
+    pub fn with_route(mut self, handler: &str, method: Method, path: &str) -> Self {
+        self.route.get_or_insert_with(|| {
+            Box::new(RouteContext {
+                handler: handler.to_owned(),
+                method,
+                path: path.to_owned(),
+            })
+        });
+        self
+    }
+
+    /// Returns the kind of error.
+    #[must_use]
+    pub fn kind(&self) -> &OpenApiErrorKind {
+        &self.kind
+    }
+
+    /// Returns the route that caused this error, if any.
+    #[must_use]
+    pub fn route(&self) -> Option<&RouteContext> {
+        self.route.as_deref()
     }
 }
 
 impl fmt::Display for OpenApiError {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-        write!(f, "{}", self.msg)
+        if let Some(route) = &self.route {
+            write!(f, "{}: ", route)?;
+        }
+        write!(f, "{}", self.kind)
+    }
+}
+
+impl fmt::Display for OpenApiErrorKind {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            OpenApiErrorKind::RefAlteration(msg)
+            | OpenApiErrorKind::StatusConflict(msg)
+            | OpenApiErrorKind::ContentTypeConflict(msg)
+            | OpenApiErrorKind::SchemaFailure(msg)
+            | OpenApiErrorKind::Other(msg) => write!(f, "{}", msg),
+            OpenApiErrorKind::MergeConflict(error) => write!(f, "{}", error),
+            OpenApiErrorKind::RouteConflict { method, path } => {
+                write!(
+                    f,
+                    "Multiple routes are documented for `{} {}`.",
+                    method, path
+                )
+            }
+            OpenApiErrorKind::DuplicateOperationId(duplicate) => write!(f, "{}", duplicate),
+            OpenApiErrorKind::Multiple(errors) => {
+                write!(f, "{} errors occurred:", errors.len())?;
+                for error in errors {
+                    write!(f, "\n- {}", error)?;
+                }
+                Ok(())
+            }
+        }
+    }
+}
+
+impl fmt::Display for RouteContext {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "`{}` ({} {})", self.handler, self.method, self.path)
     }
 }
 
-impl Error for OpenApiError {}
+impl Error for OpenApiError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match self.kind.as_ref() {
+            OpenApiErrorKind::MergeConflict(error) => Some(error),
+            _ => None,
+        }
+    }
+}
 
 impl From<MergeError> for OpenApiError {
     fn from(error: MergeError) -> Self {
-        Self::new(error.msg)
+        Self::from_kind(OpenApiErrorKind::MergeConflict(error))
+    }
+}
+
+/// An `operationId` that is used by multiple operations, which is not allowed by the
+/// OpenAPI specification.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DuplicateOperationId {
+    /// The `operationId` that is used multiple times.
+    pub operation_id: String,
+    /// The method and path of the operation that already used the `operationId`.
+    pub first: (Method, String),
+    /// The method and path of the operation that was added last.
+    pub second: (Method, String),
+}
+
+impl fmt::Display for DuplicateOperationId {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "The operationId `{}` is used by both `{} {}` and `{} {}`.",
+            self.operation_id, self.first.0, self.first.1, self.second.0, self.second.1
+        )
     }
 }
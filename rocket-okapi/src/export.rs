@@ -0,0 +1,247 @@
+use crate::handlers::OpenApiFormat;
+use crate::{OpenApiError, Result};
+use okapi::openapi3::OpenApi;
+use rocket::http::{Accept, Status};
+use rocket::local::blocking::Client;
+use rocket::{Build, Rocket};
+use std::fmt::Write;
+use std::fs;
+use std::path::Path;
+
+/// The environment variable that makes [`check`] and [`assert_up_to_date`] update the file,
+/// instead of failing when it is out of date. It has to be set to a value other than an empty
+/// string, `0` or `false`.
+pub const UPDATE_ENV_VAR: &str = "OPENAPI_UPDATE";
+
+/// The number of unchanged lines that are shown around changed lines in a diff.
+const DIFF_CONTEXT: usize = 3;
+
+/// Serialize the documentation in the given format. JSON is pretty printed.
+pub fn to_string(spec: &OpenApi, format: OpenApiFormat) -> Result<String> {
+    match format {
+        OpenApiFormat::Json => serde_json::to_string_pretty(spec)
+            .map(|json| json + "\n")
+            .map_err(|err| OpenApiError::new(format!("Could not serialize as JSON: {}", err))),
+        #[cfg(feature = "yaml")]
+        OpenApiFormat::Yaml => spec
+            .to_yaml()
+            .map_err(|err| OpenApiError::new(format!("Could not serialize as YAML: {}", err))),
+    }
+}
+
+/// Write the documentation to a file. The documentation is written as YAML if the extension of
+/// the path is `yaml` or `yml` (which requires the `yaml` feature), and as JSON otherwise.
+///
+/// Example:
+/// ```rust,ignore
+/// export::write(&openapi_get_spec![get_user, create_user], "openapi.json")?;
+/// ```
+pub fn write(spec: &OpenApi, path: impl AsRef<Path>) -> Result<()> {
+    let path = path.as_ref();
+    let content = to_string(spec, format_of(path)?)?;
+    fs::write(path, content)
+        .map_err(|err| OpenApiError::new(format!("Could not write `{}`: {}", path.display(), err)))
+}
+
+/// Get the documentation that is served by Rocket at `json_path`, for example
+/// `/v1/openapi.json`.
+///
+/// Rocket is ignited (so fairings like the [`OpenApiFairing`](crate::fairing::OpenApiFairing)
+/// run), but not launched, so no port is used. This uses a blocking client, so it can not be
+/// called from an async context.
+pub fn from_rocket(rocket: Rocket<Build>, json_path: &str) -> Result<OpenApi> {
+    let client = Client::untracked(rocket)
+        .map_err(|err| OpenApiError::new(format!("Could not ignite Rocket: {}", err)))?;
+    let response = client.get(json_path).header(Accept::JSON).dispatch();
+    if response.status() != Status::Ok {
+        return Err(OpenApiError::new(format!(
+            "Could not get the documentation from `{}`: {}",
+            json_path,
+            response.status()
+        )));
+    }
+    let json = response.into_string().unwrap_or_default();
+    serde_json::from_str(&json).map_err(|err| {
+        OpenApiError::new(format!(
+            "Could not parse the documentation from `{}`: {}",
+            json_path, err
+        ))
+    })
+}
+
+/// Check that the file contains the given documentation, in the format used by [`write`].
+///
+/// If the file is out of date, the error describes the changes (see [`okapi::diff`]) and
+/// contains a line diff of the file. If the environment variable `OPENAPI_UPDATE` is set (and not
+/// empty, `0` or `false`), the file is updated instead.
+pub fn check(spec: &OpenApi, path: impl AsRef<Path>) -> Result<()> {
+    let path = path.as_ref();
+    if update_requested() {
+        return write(spec, path);
+    }
+    let format = format_of(path)?;
+    let expected = to_string(spec, format)?;
+    let actual = match fs::read_to_string(path) {
+        Ok(actual) => actual.replace("\r\n", "\n"),
+        Err(err) => {
+            return Err(OpenApiError::new(format!(
+                "Could not read `{}`: {}\nRun with `{}=1` to create it.",
+                path.display(),
+                err,
+                UPDATE_ENV_VAR
+            )))
+        }
+    };
+    if actual == expected {
+        return Ok(());
+    }
+
+    let mut msg = format!("`{}` is out of date.\n", path.display());
+    if let Some(old) = parse(&actual, format) {
+        for change in old.diff(spec) {
+            writeln!(msg, "- {}", change).unwrap();
+        }
+    }
+    msg.push_str(&line_diff(&actual, &expected));
+    write!(msg, "Run with `{}=1` to update the file.", UPDATE_ENV_VAR).unwrap();
+    Err(OpenApiError::new(msg))
+}
+
+/// Like [`check`], but panics with the diff when the file is out of date.
+/// Useful in tests, run the test with `OPENAPI_UPDATE=1` to update the file.
+///
+/// Example:
+/// ```rust,ignore
+/// #[test]
+/// fn openapi_is_up_to_date() {
+///     let spec = export::from_rocket(create_rocket(), "/v1/openapi.json").unwrap();
+///     export::assert_up_to_date(&spec, "openapi.json");
+/// }
+/// ```
+///
+/// # Panics
+///
+/// Panics when the file is out of date or can not be read.
+pub fn assert_up_to_date(spec: &OpenApi, path: impl AsRef<Path>) {
+    if let Err(err) = check(spec, path) {
+        panic!("{}", err);
+    }
+}
+
+/// Returns `true` if [`UPDATE_ENV_VAR`] is set to update the file.
+fn update_requested() -> bool {
+    match std::env::var(UPDATE_ENV_VAR) {
+        Ok(value) => {
+            let value = value.trim();
+            !(value.is_empty() || value == "0" || value.eq_ignore_ascii_case("false"))
+        }
+        // Set, but not valid unicode, so it is not one of the values that disable updating.
+        Err(std::env::VarError::NotUnicode(_)) => true,
+        Err(std::env::VarError::NotPresent) => false,
+    }
+}
+
+fn format_of(path: &Path) -> Result<OpenApiFormat> {
+    match path.extension().and_then(|ext| ext.to_str()) {
+        #[cfg(feature = "yaml")]
+        Some("yaml" | "yml") => Ok(OpenApiFormat::Yaml),
+        #[cfg(not(feature = "yaml"))]
+        Some("yaml" | "yml") => Err(OpenApiError::new(format!(
+            "Writing `{}` as YAML requires the `yaml` feature.",
+            path.display()
+        ))),
+        _ => Ok(OpenApiFormat::Json),
+    }
+}
+
+fn parse(content: &str, format: OpenApiFormat) -> Option<OpenApi> {
+    match format {
+        OpenApiFormat::Json => serde_json::from_str(content).ok(),
+        #[cfg(feature = "yaml")]
+        OpenApiFormat::Yaml => OpenApi::from_yaml(content).ok(),
+    }
+}
+
+/// Returns the changed lines between `old` and `new`, with `-` and `+` in front of removed and
+/// added lines, and some unchanged lines around them.
+fn line_diff(old: &str, new: &str) -> String {
+    let old = old.lines().collect::<Vec<_>>();
+    let new = new.lines().collect::<Vec<_>>();
+    let prefix = old.iter().zip(&new).take_while(|(a, b)| a == b).count();
+    let suffix = old[prefix..]
+        .iter()
+        .rev()
+        .zip(new[prefix..].iter().rev())
+        .take_while(|(a, b)| a == b)
+        .count();
+    let old_changed = &old[prefix..old.len() - suffix];
+    let new_changed = &new[prefix..new.len() - suffix];
+
+    // Every line as (old line number, tag, line).
+    let mut lines = Vec::new();
+    for (i, line) in old[..prefix].iter().enumerate() {
+        lines.push((i + 1, ' ', *line));
+    }
+    for (i, tag, line) in diff_lines(old_changed, new_changed) {
+        lines.push((prefix + i + 1, tag, line));
+    }
+    for (i, line) in old[old.len() - suffix..].iter().enumerate() {
+        lines.push((old.len() - suffix + i + 1, ' ', *line));
+    }
+
+    let is_shown = |index: usize| {
+        let start = index.saturating_sub(DIFF_CONTEXT);
+        let end = (index + DIFF_CONTEXT + 1).min(lines.len());
+        lines[start..end].iter().any(|(_, tag, _)| *tag != ' ')
+    };
+    let mut diff = String::new();
+    let mut last_shown = None;
+    for (index, (line_number, tag, line)) in lines.iter().enumerate() {
+        if !is_shown(index) {
+            continue;
+        }
+        if !matches!(last_shown, Some(last) if last + 1 == index) {
+            writeln!(diff, "@@ line {} @@", line_number).unwrap();
+        }
+        writeln!(diff, "{}{}", tag, line).unwrap();
+        last_shown = Some(index);
+    }
+    diff
+}
+
+/// Diff the lines using the longest common subsequence. Returns every line as
+/// (index in `old`, tag, line). Large changes are shown as all lines removed and added.
+fn diff_lines<'a>(old: &[&'a str], new: &[&'a str]) -> Vec<(usize, char, &'a str)> {
+    let removed = old.iter().enumerate().map(|(i, line)| (i, '-', *line));
+    let added = new.iter().map(|line| (old.len(), '+', *line));
+    if old.len().saturating_mul(new.len()) > 4_000_000 {
+        return removed.chain(added).collect();
+    }
+    // `lcs[i][j]` is the length of the longest common subsequence of `old[i..]` and `new[j..]`.
+    let mut lcs = vec![vec![0usize; new.len() + 1]; old.len() + 1];
+    for i in (0..old.len()).rev() {
+        for j in (0..new.len()).rev() {
+            lcs[i][j] = if old[i] == new[j] {
+                lcs[i + 1][j + 1] + 1
+            } else {
+                lcs[i + 1][j].max(lcs[i][j + 1])
+            };
+        }
+    }
+    let mut lines = Vec::new();
+    let (mut i, mut j) = (0, 0);
+    while i < old.len() || j < new.len() {
+        if i < old.len() && j < new.len() && old[i] == new[j] {
+            lines.push((i, ' ', old[i]));
+            i += 1;
+            j += 1;
+        } else if j < new.len() && (i == old.len() || lcs[i][j + 1] >= lcs[i + 1][j]) {
+            lines.push((i, '+', new[j]));
+            j += 1;
+        } else {
+            lines.push((i, '-', old[i]));
+            i += 1;
+        }
+    }
+    lines
+}
@@ -0,0 +1,226 @@
+use crate::gen::OpenApiGenerator;
+use crate::registry::OpenApiOperation;
+use crate::route_check::RouteCheck;
+use crate::settings::{OpenApiSettings, OpenApiVersion};
+use crate::{get_openapi_routes, OpenApiError};
+use okapi::merge::marge_spec_list_with_options;
+use okapi::openapi3::{Info, OpenApi};
+use rocket::fairing::{self, Fairing, Kind};
+use rocket::{Build, Rocket, Route};
+use std::fmt;
+use std::sync::Arc;
+
+/// A fairing that documents the mounted routes and serves the OpenAPI documentation.
+///
+/// At ignite, every mounted route is matched with the operation of its `#[openapi]` route
+/// function, so routes mounted with plain `rocket::routes!` are documented under the path they
+/// are actually mounted on. The documentation of all routes is merged into one spec (using
+/// [`OpenApiSettings::merge_options`]), which is mounted at the base path of the fairing.
+/// Routes that match the operations of multiple route functions (functions with the same name
+/// and route attribute in different modules) are not documented.
+/// Mounted routes without documentation are logged as a warning, unless
+/// [`OpenApiSettings::route_check`] is set, in which case they are reported by a
+/// [`RouteCheck`].
+///
+/// Example:
+/// ```rust,ignore
+/// use rocket_okapi::fairing::OpenApiFairing;
+///
+/// rocket::build()
+///     .mount("/users", rocket::routes![get_user, create_user])
+///     .attach(OpenApiFairing::new(openapi_operations![get_user, create_user]).info(Info {
+///         title: "Users".to_owned(),
+///         version: "1.0.0".to_owned(),
+///         ..Default::default()
+///     }))
+/// ```
+#[derive(Clone)]
+pub struct OpenApiFairing {
+    operations: Vec<OpenApiOperation>,
+    // `OpenApiSettings` is not `Send` and `Sync`, so the settings are created when needed.
+    settings: Arc<dyn Fn() -> OpenApiSettings + Send + Sync>,
+    base: String,
+    info: Info,
+}
+
+impl OpenApiFairing {
+    /// Create a fairing that documents the given operations, see
+    /// [`openapi_operations!`](crate::openapi_operations).
+    #[must_use]
+    pub fn new(operations: Vec<OpenApiOperation>) -> Self {
+        OpenApiFairing {
+            operations,
+            settings: Arc::new(OpenApiSettings::default),
+            base: "/".to_owned(),
+            info: Info::default(),
+        }
+    }
+
+    /// Create a fairing that documents the operations of all routes in the binary that are
+    /// documented with `#[openapi]`, see [`registry::operations()`](crate::registry::operations).
+    /// Only the routes that are mounted are added to the documentation.
+    #[cfg(feature = "registry")]
+    #[must_use]
+    pub fn from_registry() -> Self {
+        Self::new(crate::registry::operations())
+    }
+
+    /// Set the function that creates the settings that are used to generate and serve the
+    /// documentation. A function is used because `OpenApiSettings` can not be shared between
+    /// threads.
+    #[must_use]
+    pub fn settings(
+        mut self,
+        settings: impl Fn() -> OpenApiSettings + Send + Sync + 'static,
+    ) -> Self {
+        self.settings = Arc::new(settings);
+        self
+    }
+
+    /// Set the base path the documentation is mounted on. The default is `/`.
+    /// The paths in the documentation are relative to this base path.
+    #[must_use]
+    pub fn mount_at(mut self, base: impl ToString) -> Self {
+        self.base = base.to_string();
+        self
+    }
+
+    /// Set the `info` section of the documentation, which contains the title and version.
+    #[must_use]
+    pub fn info(mut self, info: Info) -> Self {
+        self.info = info;
+        self
+    }
+
+    /// Generate the documentation for the given (mounted) routes.
+    /// Returns the documentation and the routes that are not documented.
+    pub fn generate<'r>(
+        &self,
+        routes: impl IntoIterator<Item = &'r Route>,
+    ) -> crate::Result<(OpenApi, Vec<&'r Route>)> {
+        // The spec of every mount point is generated separately and merged afterwards.
+        // Pruning and upgrading is done after merging, so all specs are merged as OpenAPI 3.0.
+        let base_settings = (self.settings)();
+        let settings = OpenApiSettings {
+            openapi_version: OpenApiVersion::V3_0,
+            prune_unused_components: false,
+            ..base_settings.clone()
+        };
+        let mut generators: Vec<(String, OpenApiGenerator)> = Vec::new();
+        let mut undocumented = Vec::new();
+        let mut errors = Vec::new();
+        for route in routes {
+            let mut candidates = self.operations.iter().filter(|op| op.matches(route));
+            let operation = match candidates.next() {
+                Some(operation) => operation,
+                None => {
+                    undocumented.push(route);
+                    continue;
+                }
+            };
+            // Rocket does not record the module of a route function, so functions with the same
+            // name and route attribute in different modules can not be told apart.
+            let others = candidates
+                .filter(|op| op.module_path != operation.module_path)
+                .map(OpenApiOperation::fn_path)
+                .collect::<Vec<_>>();
+            if !others.is_empty() {
+                log::warn!(
+                    "Route `{} {}` matches the operations of `{}` and `{}`, so it is not \
+                    documented. Rename one of the route functions.",
+                    route.method,
+                    route.uri,
+                    operation.fn_path(),
+                    others.join("`, `")
+                );
+                undocumented.push(route);
+                continue;
+            }
+            let prefix = self.relative_base(route.uri.base());
+            let index = match generators.iter().position(|(base, _)| *base == prefix) {
+                Some(index) => index,
+                None => {
+                    generators.push((prefix, OpenApiGenerator::new(&settings)));
+                    generators.len() - 1
+                }
+            };
+            if let Err(err) = operation.add_to(&mut generators[index].1) {
+                errors.push(err);
+            }
+        }
+        if !errors.is_empty() {
+            return Err(OpenApiError::multiple(errors));
+        }
+
+        let specs = generators
+            .into_iter()
+            .map(|(base, gen)| (base, gen.into_openapi()))
+            .collect::<Vec<_>>();
+        let mut spec = marge_spec_list_with_options(&specs, &base_settings.merge_options)?;
+        spec.info = self.info.clone();
+        if base_settings.prune_unused_components {
+            spec.prune_unused_components();
+        }
+        if base_settings.openapi_version == OpenApiVersion::V3_1 {
+            spec.upgrade_to_v3_1();
+        }
+        Ok((spec, undocumented))
+    }
+
+    /// Returns the mount point of a route, relative to the base path of the documentation.
+    fn relative_base(&self, route_base: &str) -> String {
+        let base = self.base.trim_end_matches('/');
+        match route_base.strip_prefix(base) {
+            Some(relative) if relative.is_empty() || relative.starts_with('/') => {
+                format!("/{}", relative.trim_start_matches('/'))
+            }
+            _ => route_base.to_owned(),
+        }
+    }
+}
+
+impl fmt::Debug for OpenApiFairing {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("OpenApiFairing")
+            .field("operations", &self.operations)
+            .field("base", &self.base)
+            .field("info", &self.info)
+            .finish_non_exhaustive()
+    }
+}
+
+#[rocket::async_trait]
+impl Fairing for OpenApiFairing {
+    fn info(&self) -> fairing::Info {
+        fairing::Info {
+            name: "OpenAPI documentation",
+            kind: Kind::Ignite,
+        }
+    }
+
+    async fn on_ignite(&self, rocket: Rocket<Build>) -> fairing::Result {
+        let (spec, undocumented) = match self.generate(rocket.routes()) {
+            Ok(result) => result,
+            Err(err) => {
+                log::error!("Could not generate OpenAPI documentation: {}", err);
+                return Err(rocket);
+            }
+        };
+        let settings = (self.settings)();
+        let rocket = match RouteCheck::from_settings(spec.clone(), &settings) {
+            Some(check) => rocket.attach(check.mount_at(&self.base)),
+            None => {
+                for route in undocumented {
+                    log::warn!(
+                        "Route `{} {}` is not documented in the OpenAPI documentation.",
+                        route.method,
+                        route.uri
+                    );
+                }
+                rocket
+            }
+        };
+        let routes = get_openapi_routes(spec, &settings);
+        Ok(rocket.mount(self.base.clone(), routes))
+    }
+}
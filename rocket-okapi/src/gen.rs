@@ -1,7 +1,11 @@
-use crate::settings::OpenApiSettings;
-use crate::OperationInfo;
-use okapi::openapi3::{Components, OpenApi, Operation, PathItem, RefOr, SecurityScheme};
-use okapi::{Map, MapEntry};
+use crate::settings::{OpenApiSettings, OpenApiVersion, RankedRoutePolicy};
+use crate::util::{produce_any_request_bodies, produce_any_responses};
+use crate::{DuplicateOperationId, OpenApiError, OpenApiErrorKind, OperationInfo, Result};
+use okapi::openapi3::{
+    Components, OpenApi, Operation, Parameter, PathItem, RefOr, RequestBody, SecurityRequirement,
+    SecurityScheme,
+};
+use okapi::Map;
 use rocket::http::Method;
 use schemars::gen::SchemaGenerator;
 use schemars::schema::SchemaObject;
@@ -11,10 +15,6 @@ use std::collections::HashMap;
 /// A struct that visits all `rocket::Route`s, and aggregates information about them.
 #[derive(Debug, Clone)]
 pub struct OpenApiGenerator {
-    // TODO: This tag should be removed in the future and settings should be used.
-    // This `allow` is just added in the mean time to make sure alle other test
-    // will be finished correctly.
-    #[allow(dead_code)]
     settings: OpenApiSettings,
     schema_generator: SchemaGenerator,
     security_schemes: Map<String, SecurityScheme>,
@@ -38,28 +38,84 @@ impl OpenApiGenerator {
         self.security_schemes.insert(name, scheme);
     }
 
+    /// Create the `operationId` for the route function with the given path, using
+    /// [`OpenApiSettings::operation_id_strategy`].
+    #[must_use]
+    pub fn operation_id(&self, fn_path: &str) -> String {
+        self.settings.operation_id_strategy.operation_id(fn_path)
+    }
+
     /// Add a new `HTTP Method` to the collection of endpoints in the `OpenApiGenerator`.
-    pub fn add_operation(&mut self, mut op: OperationInfo) {
-        if let Some(op_id) = op.operation.operation_id {
-            // TODO do this outside add_operation
-            op.operation.operation_id = Some(op_id.trim_start_matches(':').replace("::", "_"));
-        }
-        match self.operations.entry(op.path) {
-            MapEntry::Occupied(mut e) => {
-                let map = e.get_mut();
-                if map.insert(op.method, op.operation).is_some() {
-                    // This will trow a warning if 2 routes have the same path and method
-                    // This is allowed by Rocket when a ranking is given for example: `#[get("/user", rank = 2)]`
-                    // See: https://rocket.rs/v0.4/guide/requests/#forwarding
-                    println!("Warning: Operation replaced for {}:{}", op.method, e.key());
+    ///
+    /// Rocket allows multiple routes with the same path and method when they have a different
+    /// rank, for example: `#[get("/user", rank = 2)]`. What happens to those operations is
+    /// decided by [`OpenApiSettings::ranked_routes`].
+    pub fn add_operation(&mut self, op: OperationInfo) -> Result<()> {
+        let existing = self
+            .operations
+            .get_mut(&op.path)
+            .and_then(|map| map.remove(&op.method));
+        let operation = match existing {
+            None => op.operation,
+            Some(existing) => match self.settings.ranked_routes {
+                RankedRoutePolicy::Merge => {
+                    merge_operations(existing, op.operation, &self.settings.security)?
                 }
-            }
-            MapEntry::Vacant(e) => {
-                let mut map = HashMap::new();
-                map.insert(op.method, op.operation);
-                e.insert(map);
-            }
+                RankedRoutePolicy::Error => {
+                    return Err(OpenApiError::from_kind(OpenApiErrorKind::RouteConflict {
+                        method: op.method,
+                        path: op.path,
+                    }))
+                }
+                RankedRoutePolicy::KeepFirst => existing,
+                RankedRoutePolicy::KeepLast => op.operation,
+            },
         };
+        if let Some(operation_id) = &operation.operation_id {
+            self.check_operation_id(operation_id, &op.path, op.method)?;
+        }
+        self.operations
+            .entry(op.path)
+            .or_default()
+            .insert(op.method, operation);
+        Ok(())
+    }
+
+    /// Returns an error if another operation already uses the `operation_id`.
+    fn check_operation_id(&self, operation_id: &str, path: &str, method: Method) -> Result<()> {
+        for (other_path, map) in &self.operations {
+            for (other_method, other) in map {
+                if (other_path.as_str(), *other_method) == (path, method) {
+                    continue;
+                }
+                if other.operation_id.as_deref() == Some(operation_id) {
+                    return Err(OpenApiError::from_kind(
+                        OpenApiErrorKind::DuplicateOperationId(DuplicateOperationId {
+                            operation_id: operation_id.to_owned(),
+                            first: (*other_method, other_path.clone()),
+                            second: (method, path.to_owned()),
+                        }),
+                    ));
+                }
+            }
+        }
+        Ok(())
+    }
+
+    /// Add the operations of all routes in the binary that are documented with `#[openapi]`,
+    /// see [`registry::operations()`](crate::registry::operations). The routes are documented
+    /// under the path in their route attribute, without the path they are mounted on.
+    #[cfg(feature = "registry")]
+    pub fn add_registered_operations(&mut self) -> Result<()> {
+        let errors = crate::registry::operations()
+            .iter()
+            .filter_map(|operation| operation.add_to(self).err())
+            .collect::<Vec<_>>();
+        if errors.is_empty() {
+            Ok(())
+        } else {
+            Err(OpenApiError::multiple(errors))
+        }
     }
 
     /// Returns a JSON Schema object for the type `T`.
@@ -86,6 +142,9 @@ impl OpenApiGenerator {
 
         // Add the security schemes
         let mut schemes: Map<String, RefOr<SecurityScheme>> = Default::default();
+        for (name, schema) in self.settings.security_schemes.clone() {
+            schemes.insert(name, schema.into());
+        }
         for (name, schema) in self.security_schemes {
             schemes.insert(name, schema.into());
         }
@@ -96,8 +155,8 @@ impl OpenApiGenerator {
             }
         }
 
-        OpenApi {
-            openapi: "3.0.0".to_owned(),
+        let mut spec = OpenApi {
+            openapi: OpenApi::default_version(),
             paths: {
                 let mut paths = Map::new();
                 for (path, map) in self.operations {
@@ -113,9 +172,158 @@ impl OpenApiGenerator {
                 security_schemes: schemes,
                 ..Default::default()
             }),
+            security: self.settings.security.clone(),
             ..OpenApi::default()
+        };
+        if self.settings.prune_unused_components {
+            spec.prune_unused_components();
+        }
+        if self.settings.openapi_version == OpenApiVersion::V3_1 {
+            spec.upgrade_to_v3_1();
+        }
+        spec
+    }
+}
+
+/// Merge the operations of two routes with the same path and method into one operation.
+/// The properties of the `first` operation are used if they can not be combined.
+/// `global_security` are the security requirements of operations without their own.
+fn merge_operations(
+    first: Operation,
+    second: Operation,
+    global_security: &[SecurityRequirement],
+) -> Result<Operation> {
+    let mut tags = first.tags;
+    for tag in second.tags {
+        if !tags.contains(&tag) {
+            tags.push(tag);
+        }
+    }
+    let mut extensions = second.extensions;
+    extensions.extend(first.extensions);
+    let mut callbacks = second.callbacks;
+    callbacks.extend(first.callbacks);
+
+    Ok(Operation {
+        tags,
+        summary: first.summary.or(second.summary),
+        description: first.description.or(second.description),
+        external_docs: first.external_docs.or(second.external_docs),
+        operation_id: first.operation_id.or(second.operation_id),
+        parameters: merge_parameters(first.parameters, second.parameters),
+        request_body: merge_request_bodies(first.request_body, second.request_body)?,
+        responses: produce_any_responses(first.responses, second.responses)?,
+        callbacks,
+        deprecated: first.deprecated && second.deprecated,
+        security: merge_security(first.security, second.security, global_security),
+        servers: first.servers.or(second.servers),
+        extensions,
+    })
+}
+
+/// Combine the parameters of both operations.
+/// Parameters that are only used by one of the operations are not required.
+fn merge_parameters(
+    first: Vec<RefOr<Parameter>>,
+    second: Vec<RefOr<Parameter>>,
+) -> Vec<RefOr<Parameter>> {
+    fn key(parameter: &RefOr<Parameter>) -> (&str, &str) {
+        match parameter {
+            RefOr::Ref(reference) => (&reference.reference, ""),
+            RefOr::Object(parameter) => (&parameter.name, &parameter.location),
+        }
+    }
+    fn make_optional(mut parameter: RefOr<Parameter>) -> RefOr<Parameter> {
+        if let RefOr::Object(parameter) = &mut parameter {
+            // Path parameters are always required.
+            if parameter.location != "path" {
+                parameter.required = false;
+            }
+        }
+        parameter
+    }
+
+    let mut parameters = Vec::new();
+    for parameter in &first {
+        match second.iter().find(|other| key(other) == key(parameter)) {
+            Some(RefOr::Object(other)) => {
+                let mut parameter = parameter.clone();
+                if let RefOr::Object(parameter) = &mut parameter {
+                    parameter.required &= other.required;
+                }
+                parameters.push(parameter);
+            }
+            Some(RefOr::Ref(_)) => parameters.push(parameter.clone()),
+            None => parameters.push(make_optional(parameter.clone())),
+        }
+    }
+    for parameter in second {
+        if !first.iter().any(|other| key(other) == key(&parameter)) {
+            parameters.push(make_optional(parameter));
+        }
+    }
+    parameters
+}
+
+/// Combine the request bodies of both operations.
+/// The request body is not required if one of the operations does not need it.
+fn merge_request_bodies(
+    first: Option<RefOr<RequestBody>>,
+    second: Option<RefOr<RequestBody>>,
+) -> Result<Option<RefOr<RequestBody>>> {
+    fn make_optional(mut body: RefOr<RequestBody>) -> RefOr<RequestBody> {
+        if let RefOr::Object(body) = &mut body {
+            body.required = false;
+        }
+        body
+    }
+
+    Ok(match (first, second) {
+        (Some(RefOr::Object(first)), Some(RefOr::Object(second))) => {
+            Some(produce_any_request_bodies(first, second).into())
+        }
+        (Some(first), Some(second)) if first == second => Some(first),
+        (Some(_), Some(_)) => {
+            return Err(OpenApiError::from_kind(OpenApiErrorKind::RefAlteration(
+                "Merging Ref request bodies is not supported.".to_owned(),
+            )))
+        }
+        (Some(body), None) | (None, Some(body)) => Some(make_optional(body)),
+        (None, None) => None,
+    })
+}
+
+/// Combine the security requirements of both operations.
+/// The security requirements of an operation are alternatives, so the requirements of both
+/// operations are combined. An operation without security requirements (`None`) uses the
+/// `global_security`. When one of the operations does not need any security, an empty
+/// requirement is added to make security optional.
+fn merge_security(
+    first: Option<Vec<SecurityRequirement>>,
+    second: Option<Vec<SecurityRequirement>>,
+    global_security: &[SecurityRequirement],
+) -> Option<Vec<SecurityRequirement>> {
+    let (first, second) = match (first, second) {
+        // Both inherit the global security, so the merged operation does as well.
+        (None, None) => return None,
+        (first, second) => (
+            first.unwrap_or_else(|| global_security.to_vec()),
+            second.unwrap_or_else(|| global_security.to_vec()),
+        ),
+    };
+    let mut security = Vec::new();
+    for requirement in [first.clone(), second.clone()].concat() {
+        if !security.contains(&requirement) {
+            security.push(requirement);
+        }
+    }
+    if first.is_empty() || second.is_empty() {
+        let optional = SecurityRequirement::new();
+        if !security.contains(&optional) {
+            security.push(optional);
         }
     }
+    Some(security)
 }
 
 fn set_operation(path_item: &mut PathItem, method: Method, op: Operation) {
@@ -44,7 +44,10 @@ impl ContentHandler<Vec<u8>> {
 impl<R: AsRef<[u8]> + Clone + Send + Sync + 'static> ContentHandler<R> {
     /// Create a `rocket::Route` from the current `ContentHandler`.
     pub fn into_route(self, path: impl AsRef<str>) -> Route {
-        Route::new(Method::Get, path.as_ref(), self)
+        let mut route = Route::new(Method::Get, path.as_ref(), self);
+        // Named, so these routes are not reported by the `RouteCheck`.
+        route.name = Some("rocket_okapi::content".into());
+        route
     }
 }
 
@@ -1,47 +1,288 @@
 use okapi::openapi3::{OpenApi, Server};
-use rocket::http::Method;
-use rocket::response::content::RawJson;
+use rocket::http::{ContentType, Header, Method, Status};
 use rocket::route::{Handler, Outcome};
-use rocket::{Data, Request, Route};
+use rocket::{Data, Request, Response, Route};
+use sha2::{Digest, Sha256};
+use std::collections::HashMap;
+use std::io::Cursor;
+use std::sync::{Arc, Mutex};
 
-/// A handler type that is used to serve the `openapi.json` files.
+/// The format used to serve the OpenAPI documentation.
+///
+/// This enum is `non_exhaustive`, as the `Yaml` variant only exists when the `yaml` feature is
+/// enabled.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+#[non_exhaustive]
+pub enum OpenApiFormat {
+    /// Serve the documentation as (pretty printed) JSON.
+    #[default]
+    Json,
+    /// Serve the documentation as YAML.
+    #[cfg(feature = "yaml")]
+    Yaml,
+}
+
+impl OpenApiFormat {
+    /// Returns the format that is requested by the `Accept` header of the request, if any.
+    fn from_accept(req: &Request<'_>) -> Option<Self> {
+        let media_type = req.accept()?.preferred().media_type();
+        if media_type.is_json() {
+            return Some(OpenApiFormat::Json);
+        }
+        #[cfg(feature = "yaml")]
+        if matches!(media_type.sub().as_str(), "yaml" | "x-yaml")
+            && matches!(media_type.top().as_str(), "application" | "text")
+        {
+            return Some(OpenApiFormat::Yaml);
+        }
+        None
+    }
+}
+
+/// The `Content-Encoding` used for a response.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+enum Encoding {
+    Identity,
+    #[cfg(feature = "gzip")]
+    Gzip,
+    #[cfg(feature = "brotli")]
+    Brotli,
+}
+
+impl Encoding {
+    /// Returns the best encoding that is accepted by the `Accept-Encoding` header of the request.
+    #[cfg_attr(
+        not(any(feature = "gzip", feature = "brotli")),
+        allow(unused_variables)
+    )]
+    fn from_accept_encoding(req: &Request<'_>) -> Self {
+        #[cfg(feature = "brotli")]
+        if accepts_encoding(req, "br") {
+            return Encoding::Brotli;
+        }
+        #[cfg(feature = "gzip")]
+        if accepts_encoding(req, "gzip") {
+            return Encoding::Gzip;
+        }
+        Encoding::Identity
+    }
+
+    fn header_value(self) -> Option<&'static str> {
+        match self {
+            Encoding::Identity => None,
+            #[cfg(feature = "gzip")]
+            Encoding::Gzip => Some("gzip"),
+            #[cfg(feature = "brotli")]
+            Encoding::Brotli => Some("br"),
+        }
+    }
+
+    fn encode(self, body: &[u8]) -> Vec<u8> {
+        match self {
+            Encoding::Identity => body.to_vec(),
+            #[cfg(feature = "gzip")]
+            Encoding::Gzip => {
+                use std::io::Write;
+                let mut encoder =
+                    flate2::write::GzEncoder::new(Vec::new(), flate2::Compression::best());
+                encoder
+                    .write_all(body)
+                    .and_then(|_| encoder.finish())
+                    .expect("Could not gzip compress content.")
+            }
+            #[cfg(feature = "brotli")]
+            Encoding::Brotli => {
+                let mut output = Vec::new();
+                let params = brotli::enc::BrotliEncoderParams::default();
+                brotli::BrotliCompress(&mut &body[..], &mut output, &params)
+                    .expect("Could not brotli compress content.");
+                output
+            }
+        }
+    }
+}
+
+/// Check if the `Accept-Encoding` header of the request accepts the content coding.
+#[cfg(any(feature = "gzip", feature = "brotli"))]
+fn accepts_encoding(req: &Request<'_>, name: &str) -> bool {
+    req.headers()
+        .get("Accept-Encoding")
+        .flat_map(|value| value.split(','))
+        .map(|coding| {
+            let mut parts = coding.split(';').map(str::trim);
+            let coding = parts.next().unwrap_or_default();
+            // Codings with `q=0` are explicitly not accepted.
+            let refused = parts
+                .filter_map(|param| param.strip_prefix("q="))
+                .any(|q| q.parse::<f32>().map(|q| q <= 0.0).unwrap_or(false));
+            (coding, !refused)
+        })
+        .find(|(coding, _)| coding.eq_ignore_ascii_case(name) || *coding == "*")
+        .map(|(_, accepted)| accepted)
+        .unwrap_or(false)
+}
+
+/// Identifies a cached response: the base path the route is mounted on, the format,
+/// whether the output is minified and the encoding.
+type CacheKey = (String, OpenApiFormat, bool, Encoding);
+
+/// A serialized (and possibly compressed) spec.
+struct CachedResponse {
+    content_type: ContentType,
+    body: Arc<[u8]>,
+    etag: String,
+}
+
+/// A handler type that is used to serve the `openapi.json` (and `openapi.yaml`) files.
+///
+/// The format is selected using the `Accept` header of the request. If the request does not
+/// ask for a supported format, the default format of the handler is used.
+///
+/// The spec is only serialized once for every base path the route is mounted on, after that
+/// the cached response is used. Responses contain an `ETag` header, so clients can use
+/// `If-None-Match` to only download the spec when it changed.
+/// With the `gzip` and `brotli` features, the response is compressed when the client accepts it.
+/// JSON responses are minified when the `minified` query parameter is set
+/// (`openapi.json?minified`).
 #[derive(Clone)]
 pub struct OpenApiHandler {
     spec: OpenApi,
+    format: OpenApiFormat,
+    cache: Arc<Mutex<HashMap<CacheKey, Arc<CachedResponse>>>>,
 }
 
 impl OpenApiHandler {
     /// Create a new handler from an API spec.
     #[must_use]
     pub fn new(spec: OpenApi) -> Self {
-        OpenApiHandler { spec }
+        OpenApiHandler {
+            spec,
+            format: OpenApiFormat::default(),
+            cache: Default::default(),
+        }
+    }
+
+    /// Set the format that is used when the `Accept` header does not request a supported format.
+    #[must_use]
+    pub fn with_format(mut self, format: OpenApiFormat) -> Self {
+        self.format = format;
+        self
     }
 
     /// Create a new route from this `OpenApiHandler`.
     pub fn into_route(self, path: impl AsRef<str>) -> Route {
-        Route::new(Method::Get, path.as_ref(), self)
+        let mut route = Route::new(Method::Get, path.as_ref(), self);
+        // Named, so these routes are not reported by the `RouteCheck`.
+        route.name = Some("rocket_okapi::openapi".into());
+        route
     }
+
+    /// Get the cached response, or create (and cache) it.
+    fn cached_response(&self, key: CacheKey) -> Arc<CachedResponse> {
+        if let Some(response) = self.cache.lock().unwrap().get(&key) {
+            return response.clone();
+        }
+        let (base_path, format, minified, encoding) = key.clone();
+        let response = if encoding == Encoding::Identity {
+            self.serialize(&base_path, format, minified)
+        } else {
+            let identity = self.cached_response((base_path, format, minified, Encoding::Identity));
+            let body: Arc<[u8]> = encoding.encode(&identity.body).into();
+            CachedResponse {
+                content_type: identity.content_type.clone(),
+                etag: etag(&body),
+                body,
+            }
+        };
+        let response = Arc::new(response);
+        self.cache.lock().unwrap().insert(key, response.clone());
+        response
+    }
+
+    fn serialize(&self, base_path: &str, format: OpenApiFormat, minified: bool) -> CachedResponse {
+        let mut spec = self.spec.clone();
+        if spec.servers.is_empty() && base_path != "/" {
+            spec.servers.push(Server {
+                url: base_path.to_owned(),
+                ..okapi::openapi3::Server::default()
+            })
+        }
+
+        let (content_type, body) = match format {
+            OpenApiFormat::Json => {
+                let json = if minified {
+                    serde_json::to_string(&spec)
+                } else {
+                    serde_json::to_string_pretty(&spec)
+                };
+                let json = json.expect("Could not serialize content as JSON.");
+                (ContentType::JSON, json)
+            }
+            #[cfg(feature = "yaml")]
+            OpenApiFormat::Yaml => {
+                let yaml = spec
+                    .to_yaml()
+                    .expect("Could not serialize content as YAML.");
+                (ContentType::new("application", "yaml"), yaml)
+            }
+        };
+        let body: Arc<[u8]> = body.into_bytes().into();
+        CachedResponse {
+            content_type,
+            etag: etag(&body),
+            body,
+        }
+    }
+}
+
+/// Create a strong `ETag` for the body, the SHA-256 hash of the body. The hash does not depend on
+/// the Rust version or the process, so the `ETag` stays the same after a restart or an update, as
+/// long as the body is the same.
+fn etag(body: &[u8]) -> String {
+    format!("\"{:x}\"", Sha256::digest(body))
+}
+
+/// Check if the `If-None-Match` header of the request matches the `ETag`.
+fn etag_matches(req: &Request<'_>, etag: &str) -> bool {
+    req.headers()
+        .get("If-None-Match")
+        .flat_map(|value| value.split(','))
+        .map(str::trim)
+        .any(|tag| tag == "*" || tag.trim_start_matches("W/") == etag)
 }
 
 #[rocket::async_trait]
 impl Handler for OpenApiHandler {
     async fn handle<'r>(&self, req: &'r Request<'_>, _: Data<'r>) -> Outcome<'r> {
-        let mut spec = self.spec.clone();
         let base_path = req
             .route()
             .expect("Routing should already have occurred.")
             .uri
-            .base();
+            .base()
+            .to_string();
+        let format = OpenApiFormat::from_accept(req).unwrap_or(self.format);
+        // `?minified` and `?minified=true` both minify the output.
+        let minified = req
+            .query_value::<bool>("minified")
+            .map(|minified| minified.unwrap_or(true))
+            .unwrap_or(false);
+        let encoding = Encoding::from_accept_encoding(req);
+        let cached = self.cached_response((base_path, format, minified, encoding));
 
-        if spec.servers.is_empty() && base_path != "/" {
-            spec.servers.push(Server {
-                url: base_path.to_owned(),
-                ..okapi::openapi3::Server::default()
-            })
+        let mut response = Response::build();
+        response
+            .header(Header::new("ETag", cached.etag.clone()))
+            .header(Header::new("Vary", "Accept, Accept-Encoding"));
+        if etag_matches(req, &cached.etag) {
+            response.status(Status::NotModified);
+        } else {
+            response
+                .header(cached.content_type.clone())
+                .sized_body(cached.body.len(), Cursor::new(cached.body.clone()));
+            if let Some(content_encoding) = encoding.header_value() {
+                response.header(Header::new("Content-Encoding", content_encoding));
+            }
         }
-
-        let json =
-            serde_json::to_string_pretty(&spec).expect("Could not serialize content as JSON.");
-        Outcome::from(req, RawJson(json))
+        Outcome::Success(response.finalize())
     }
 }
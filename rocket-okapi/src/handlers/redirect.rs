@@ -20,7 +20,10 @@ impl RedirectHandler {
 
     /// Create a new `Route` from this `Handler`.
     pub fn into_route(self, path: impl AsRef<str>) -> Route {
-        Route::new(Method::Get, path.as_ref(), self)
+        let mut route = Route::new(Method::Get, path.as_ref(), self);
+        // Named, so these routes are not reported by the `RouteCheck`.
+        route.name = Some("rocket_okapi::redirect".into());
+        route
     }
 }
 
@@ -70,12 +70,40 @@
 //! - `openapi_get_routes_spec![...]`: `(Vec<rocket::Route>, okapi::openapi3::OpenApi)`
 //! - `openapi_get_spec![...]`: `okapi::openapi3::OpenApi`
 //!
+//! Alternatively, the [`OpenApiFairing`](fairing::OpenApiFairing) documents the routes that are
+//! mounted, using their operations from `openapi_operations![...]`. With the `registry` feature,
+//! `OpenApiFairing::from_registry()` documents all mounted routes without listing them.
+//!
+//! ## Exporting the documentation
+//! The [`export`] module writes the documentation to a JSON or YAML file without launching the
+//! server, for example to commit `openapi.json` from CI. [`export::write()`] takes a spec from
+//! `openapi_get_spec![...]`, and [`export::from_rocket()`] gets the documentation served by a
+//! `Rocket<Build>`.
+//!
+//! To fail when the committed file differs from the generated documentation, call
+//! [`export::assert_up_to_date()`] from a test. It panics with a readable diff when the file is out
+//! of date. No separate binary is provided, `cargo test` is the check. Run the tests with
+//! `OPENAPI_UPDATE=1` to update the file instead.
+//! ```rust,ignore
+//! #[test]
+//! fn openapi_is_up_to_date() {
+//!     let spec = rocket_okapi::export::from_rocket(create_rocket(), "/v1/openapi.json").unwrap();
+//!     rocket_okapi::export::assert_up_to_date(&spec, "openapi.json");
+//! }
+//! ```
+//!
 //! ## FAQ
 //!
 //! All FAQ questions and answers can be found in [README.md](https://github.com/GREsau/okapi/tree/master#faq).
 
 mod error;
 
+/// Contains functions to write the OpenAPI documentation to a file without launching the server,
+/// and to check that a committed file is up to date.
+pub mod export;
+/// Contains the `OpenApiFairing`, which documents the mounted routes and serves the
+/// documentation.
+pub mod fairing;
 /// Contains the `Generator` struct, which you can use to manually control the way a struct is
 /// represented in the documentation.
 pub mod gen;
@@ -85,12 +113,17 @@ pub mod handlers;
 /// Contains the functions and structs required to display the RapiDoc UI.
 #[cfg(feature = "rapidoc")]
 pub mod rapidoc;
+/// Contains the `OpenApiOperation` struct, which links a route to its documentation.
+pub mod registry;
 /// This module contains several traits that correspond to the `Rocket` traits pertaining to request
 /// guards and responses
 pub mod request;
 /// Contains the trait `OpenApiResponder`, meaning that a response implementing this trait can be
 /// documented.
 pub mod response;
+/// Contains the `RouteCheck` fairing, which checks that the documentation matches the mounted
+/// routes.
+pub mod route_check;
 /// Contains then `OpenApiSettings` struct, which can be used to customize the behavior of a
 /// `Generator`.
 pub mod settings;
@@ -99,6 +132,8 @@ pub mod settings;
 pub mod swagger_ui;
 /// Assorted function that are used throughout the application.
 pub mod util;
+/// Contains the `RequestValidator` fairing, which validates requests against the documentation.
+pub mod validation;
 
 pub use error::*;
 /// Re-export Okapi
@@ -119,6 +154,8 @@ pub struct OperationInfo {
 /// Convert OpenApi object to routable endpoint.
 ///
 /// Used to serve an `OpenApi` object as an `openapi.json` file in Rocket.
+/// With the `yaml` feature, the yaml specification is returned when it is requested using the
+/// `Accept` header. Use [`get_openapi_routes`] to also get the route for the `yaml_path`.
 pub fn get_openapi_route(
     spec: okapi::openapi3::OpenApi,
     settings: &settings::OpenApiSettings,
@@ -126,6 +163,62 @@ pub fn get_openapi_route(
     handlers::OpenApiHandler::new(spec).into_route(&settings.json_path)
 }
 
+/// Convert OpenApi object to routable endpoints.
+///
+/// Used to serve an `OpenApi` object as an `openapi.json` file, and with the `yaml` feature
+/// also as an `openapi.yaml` file (see `OpenApiSettings::yaml_path`) in Rocket.
+pub fn get_openapi_routes(
+    spec: okapi::openapi3::OpenApi,
+    settings: &settings::OpenApiSettings,
+) -> Vec<rocket::Route> {
+    #[cfg(feature = "yaml")]
+    if let Some(yaml_path) = &settings.yaml_path {
+        return vec![
+            handlers::OpenApiHandler::new(spec.clone()).into_route(&settings.json_path),
+            handlers::OpenApiHandler::new(spec)
+                .with_format(handlers::OpenApiFormat::Yaml)
+                .into_route(yaml_path),
+        ];
+    }
+    vec![get_openapi_route(spec, settings)]
+}
+
+/// Get routes that serve filtered versions of the `OpenApi` object, each at their own path.
+///
+/// For each `(json_path, predicate)` pair, only the operations for which the predicate returns
+/// `true` are kept (see [`OpenApi::retain_operations`](okapi::openapi3::OpenApi::retain_operations)),
+/// and the components that are no longer used are removed.
+///
+/// Example:
+/// ```rust,ignore
+/// use rocket_okapi::okapi::filter::{has_tag, path_glob};
+///
+/// let (routes, spec) = openapi_get_routes_spec![get_user, get_metrics];
+/// let is_internal = path_glob("/internal/**");
+/// let spec_routes = get_filtered_openapi_routes(
+///     &spec,
+///     &[
+///         ("/public.json", &|path, method, op| !is_internal(path, method, op)),
+///         ("/internal.json", &is_internal),
+///     ],
+/// );
+/// rocket::build().mount("/", routes).mount("/", spec_routes)
+/// ```
+pub fn get_filtered_openapi_routes(
+    spec: &okapi::openapi3::OpenApi,
+    filters: &[(&str, &okapi::filter::OperationPredicate<'_>)],
+) -> Vec<rocket::Route> {
+    filters
+        .iter()
+        .map(|(json_path, predicate)| {
+            let mut spec = spec.clone();
+            spec.retain_operations(predicate);
+            spec.prune_unused_components();
+            handlers::OpenApiHandler::new(spec).into_route(json_path)
+        })
+        .collect()
+}
+
 /// Mount endpoints and mount merged OpenAPI documentation.
 ///
 /// This macro just makes to code look cleaner and improves readability
@@ -136,6 +229,8 @@ pub fn get_openapi_route(
 /// - base_path: `&str`, `String` or [`Uri`](rocket::http::uri::Uri). (Anything that implements `ToString`)
 ///   Anything accepted by [`mount()`](https://docs.rs/rocket/0.5.1/rocket/struct.Rocket.html#method.mount)
 /// - openapi_settings: `OpenApiSettings` (use `OpenApiSettings::default()` if default settings are okay for you),
+///   the `merge_options` are used to merge the documentation together, and the `route_check`
+///   adds a [`RouteCheck`](route_check::RouteCheck) for the merged documentation,
 /// - List of (0 or more):
 ///   - path:  `&str`, `String` or [`Uri`](rocket::http::uri::Uri).
 ///     Anything accepted by `mount()` (`base_path` should not be included).
@@ -167,17 +262,27 @@ macro_rules! mount_endpoints_and_merged_docs {
             openapi_list.push(($path, openapi));
         })*
         // Combine all OpenApi documentation into one struct.
-        let openapi_docs = match rocket_okapi::okapi::merge::marge_spec_list(&openapi_list){
+        let openapi_docs = match rocket_okapi::okapi::merge::marge_spec_list_with_options(
+            &openapi_list,
+            &$openapi_settings.merge_options,
+        ){
             Ok(docs) => docs,
             Err(err) => panic!("Could not merge OpenAPI spec: {}", err),
         };
+        // Check the documentation against the mounted routes, if enabled.
+        if let Some(check) = rocket_okapi::route_check::RouteCheck::from_settings(
+            openapi_docs.clone(),
+            &$openapi_settings,
+        ) {
+            $rocket_builder = $rocket_builder.attach(check.mount_at(&base_path));
+        }
         // Add OpenApi route
         $rocket_builder = $rocket_builder.mount(
             $base_path,
-            vec![rocket_okapi::get_openapi_route(
+            rocket_okapi::get_openapi_routes(
                 openapi_docs,
                 &$openapi_settings,
-            )],
+            ),
         );
     }};
 }
@@ -0,0 +1,101 @@
+use crate::gen::OpenApiGenerator;
+use rocket::http::uri::Origin;
+use rocket::http::Method;
+use rocket::Route;
+
+#[cfg(feature = "registry")]
+#[doc(hidden)]
+pub use inventory;
+
+/// The operation of a route documented with `#[openapi]`.
+///
+/// This links a Rocket route to the function that adds its documentation to an
+/// [`OpenApiGenerator`]. It is created by the `#[openapi]` macro, use
+/// [`openapi_operations!`](crate::openapi_operations) to get the operations of routes.
+/// With the `registry` feature, all operations in the binary are also available through
+/// [`operations()`].
+#[derive(Debug, Clone, Copy)]
+pub struct OpenApiOperation {
+    /// The name of the route function.
+    pub handler: &'static str,
+    /// The `module_path!()` of the module that contains the route function.
+    pub module_path: &'static str,
+    /// The method of the route.
+    pub method: Method,
+    /// The uri of the route, as written in the route attribute (without the mount point).
+    pub uri: &'static str,
+    /// Adds the operation to the generator, the second argument is the path of the function
+    /// that is used to create the `operationId`.
+    pub add_operation: fn(&mut OpenApiGenerator, String) -> crate::Result<()>,
+}
+
+impl OpenApiOperation {
+    /// The path of the route function, relative to the crate root. For example `users::get_all`.
+    /// This is the same path that is used by `openapi_get_spec!` when the route is listed
+    /// from the crate root.
+    #[must_use]
+    pub fn fn_path(&self) -> String {
+        match self.module_path.split_once("::") {
+            Some((_crate_name, module)) => format!("{}::{}", module, self.handler),
+            None => self.handler.to_owned(),
+        }
+    }
+
+    /// Add the operation to the generator.
+    pub fn add_to(&self, gen: &mut OpenApiGenerator) -> crate::Result<()> {
+        (self.add_operation)(gen, self.fn_path())
+    }
+
+    /// Check if this is the operation of the mounted Rocket `route`.
+    ///
+    /// The name of the route function, the method and the uri are compared. Rocket does not
+    /// record the module of a route function, so the operations of functions with the same name
+    /// and route attribute in different modules match the same routes.
+    #[must_use]
+    pub fn matches(&self, route: &Route) -> bool {
+        if route.name.as_deref() != Some(self.handler) || route.method != self.method {
+            return false;
+        }
+        match Origin::parse_route(self.uri) {
+            Ok(uri) => uri.into_normalized() == route.uri.unmounted_origin,
+            Err(_) => false,
+        }
+    }
+}
+
+#[cfg(feature = "registry")]
+inventory::collect!(OpenApiOperation);
+
+/// Returns the operations of all routes in the binary that are documented with `#[openapi]`,
+/// including the routes of other crates. The operations are sorted by the path of their route
+/// function.
+#[cfg(feature = "registry")]
+#[must_use]
+pub fn operations() -> Vec<OpenApiOperation> {
+    let mut operations = inventory::iter::<OpenApiOperation>
+        .into_iter()
+        .copied()
+        .collect::<Vec<_>>();
+    operations.sort_by_key(|operation| (operation.module_path, operation.handler));
+    operations
+}
+
+/// Used by the `#[openapi]` macro to add an operation to the registry.
+/// Without the `registry` feature this does nothing.
+#[cfg(feature = "registry")]
+#[doc(hidden)]
+#[macro_export]
+macro_rules! __register_operation {
+    ($operation:ident) => {
+        $crate::registry::inventory::submit!($operation);
+    };
+}
+
+/// Used by the `#[openapi]` macro to add an operation to the registry.
+/// Without the `registry` feature this does nothing.
+#[cfg(not(feature = "registry"))]
+#[doc(hidden)]
+#[macro_export]
+macro_rules! __register_operation {
+    ($operation:ident) => {};
+}
@@ -0,0 +1,35 @@
+use super::OpenApiHeader;
+use crate::gen::OpenApiGenerator;
+use okapi::openapi3::SchemaObject;
+use rocket::http::{Accept, ContentType, Header};
+
+// Implement `OpenApiHeader` for the types that implement `Into<Header>` in Rocket.
+
+impl OpenApiHeader for Header<'_> {
+    fn header_schema(gen: &mut OpenApiGenerator) -> SchemaObject {
+        gen.json_schema::<String>()
+    }
+}
+
+impl OpenApiHeader for ContentType {
+    fn header_schema(gen: &mut OpenApiGenerator) -> SchemaObject {
+        gen.json_schema::<String>()
+    }
+}
+
+impl OpenApiHeader for Accept {
+    fn header_schema(gen: &mut OpenApiGenerator) -> SchemaObject {
+        gen.json_schema::<String>()
+    }
+}
+
+/// A header that is not always added to the response.
+impl<T: OpenApiHeader> OpenApiHeader for Option<T> {
+    fn header_schema(gen: &mut OpenApiGenerator) -> SchemaObject {
+        T::header_schema(gen)
+    }
+
+    fn header_required() -> bool {
+        false
+    }
+}
@@ -1,8 +1,9 @@
+mod header_impls;
 mod responder_impls;
 
 use super::gen::OpenApiGenerator;
 use super::Result;
-use okapi::openapi3::Responses;
+use okapi::openapi3::{Responses, SchemaObject};
 
 /// See `OpenApiResponderInner`. This is a wrapper around
 /// `OpenApiResponderInner` that ensures the implementor is a
@@ -30,3 +31,60 @@ pub trait OpenApiResponderInner {
     /// rendered in `openapi.json` format.
     fn responses(gen: &mut OpenApiGenerator) -> Result<Responses>;
 }
+
+/// Implementing this trait means that a field of this type can be documented as a response
+/// header by `#[derive(OpenApiResponder)]`.
+///
+/// The name of the header is set with `#[openapi_header(name = "...")]`, and
+/// `#[openapi_header(skip)]` leaves a header undocumented. Fields marked with Rocket's
+/// `#[response(ignore)]` are not headers, so they are not documented either:
+/// ```rust
+/// use rocket::http::Header;
+/// use rocket::serde::json::Json;
+/// use rocket_okapi::OpenApiResponder;
+///
+/// #[derive(rocket::Responder, OpenApiResponder)]
+/// pub struct Created {
+///     inner: Json<String>,
+///     #[openapi_header(name = "ETag")]
+///     etag: Header<'static>,
+///     #[response(ignore)]
+///     #[openapi_header(name = "X-Source")]
+///     source: String,
+/// }
+/// ```
+///
+/// Only `name` and `skip` are accepted by `#[openapi_header]`:
+/// ```rust,compile_fail
+/// # use rocket::http::Header;
+/// # use rocket::serde::json::Json;
+/// # use rocket_okapi::OpenApiResponder;
+/// #[derive(OpenApiResponder)]
+/// pub struct Created {
+///     inner: Json<String>,
+///     #[openapi_header(ignore)]
+///     etag: Header<'static>,
+/// }
+/// ```
+///
+/// And the name of a header can not be set with `#[response]`:
+/// ```rust,compile_fail
+/// # use rocket::http::Header;
+/// # use rocket::serde::json::Json;
+/// # use rocket_okapi::OpenApiResponder;
+/// #[derive(OpenApiResponder)]
+/// pub struct Created {
+///     inner: Json<String>,
+///     #[response(name = "ETag")]
+///     etag: Header<'static>,
+/// }
+/// ```
+pub trait OpenApiHeader {
+    /// Create the schema of the value of the header.
+    fn header_schema(gen: &mut OpenApiGenerator) -> SchemaObject;
+
+    /// Returns `true` if the header is always added to the response. The default is `true`.
+    fn header_required() -> bool {
+        true
+    }
+}
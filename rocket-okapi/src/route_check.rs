@@ -0,0 +1,256 @@
+use crate::settings::OpenApiSettings;
+use okapi::filter::glob_matches;
+use okapi::openapi3::OpenApi;
+use rocket::fairing::{self, Fairing, Kind};
+use rocket::http::Method;
+use rocket::{Orbit, Rocket, Route};
+use std::fmt;
+
+/// The name prefix of the routes created by `rocket_okapi`, like the routes that serve the
+/// OpenAPI documentation, Swagger UI and RapiDoc. These routes are never reported.
+const ROUTE_NAME_PREFIX: &str = "rocket_okapi::";
+
+/// What a [`RouteCheck`] does when it finds undocumented routes or stale operations.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum RouteCheckMode {
+    /// Log a warning and continue.
+    #[default]
+    Warn,
+    /// Log an error and shut down Rocket.
+    Fail,
+}
+
+/// A fairing that checks at liftoff that the served OpenAPI documentation matches the routes
+/// that are actually mounted.
+///
+/// It reports every mounted route under the base path (see [`mount_at`](RouteCheck::mount_at))
+/// that is not documented, and every documented operation for which no route is mounted (for example because the path a route is mounted on changed).
+/// Routes and operations can be ignored with [`allow`](RouteCheck::allow). The routes created by
+/// `rocket_okapi` itself, like the route of the `openapi.json` and the Swagger UI, are always
+/// ignored.
+///
+/// Use [`OpenApiSettings::route_check`] to add this check when using
+/// [`mount_endpoints_and_merged_docs!`](crate::mount_endpoints_and_merged_docs) or
+/// [`OpenApiFairing`](crate::fairing::OpenApiFairing).
+///
+/// Example:
+/// ```rust,ignore
+/// let (routes, spec) = openapi_get_routes_spec![get_user, create_user];
+/// rocket::build()
+///     .mount("/api", routes)
+///     .mount("/api", get_openapi_routes(spec.clone(), &settings))
+///     .attach(
+///         RouteCheck::new(spec)
+///             .mount_at("/api")
+///             .allow("GET /metrics")
+///             .mode(RouteCheckMode::Fail),
+///     )
+/// ```
+#[derive(Debug, Clone)]
+pub struct RouteCheck {
+    spec: OpenApi,
+    base: String,
+    allow: Vec<String>,
+    mode: RouteCheckMode,
+}
+
+impl RouteCheck {
+    /// Create a check for the given (served) documentation.
+    #[must_use]
+    pub fn new(spec: OpenApi) -> Self {
+        RouteCheck {
+            spec,
+            base: "/".to_owned(),
+            allow: Vec::new(),
+            mode: RouteCheckMode::default(),
+        }
+    }
+
+    /// Create a check using [`OpenApiSettings::route_check`] and
+    /// [`OpenApiSettings::route_check_allow`].
+    /// Returns `None` if the check is not enabled in the settings.
+    #[must_use]
+    pub fn from_settings(spec: OpenApi, settings: &OpenApiSettings) -> Option<Self> {
+        let mode = settings.route_check?;
+        let check = settings
+            .route_check_allow
+            .iter()
+            .fold(Self::new(spec).mode(mode), |check, pattern| {
+                check.allow(pattern)
+            });
+        Some(check)
+    }
+
+    /// Set the base path the documentation is mounted on, the paths in the documentation are
+    /// relative to this path. Only the routes mounted under this path are checked, so the
+    /// documentation of every base path can be checked separately. The default is `/`.
+    #[must_use]
+    pub fn mount_at(mut self, base: impl ToString) -> Self {
+        self.base = base.to_string();
+        self
+    }
+
+    /// Ignore the routes and operations that match the pattern. The pattern is a path glob
+    /// (see [`okapi::filter::path_glob`]), optionally preceded by a method. For example
+    /// `/swagger/**` or `GET /health`. Paths use the OpenAPI syntax (`/users/{id}`) and include
+    /// the path the route is mounted on.
+    #[must_use]
+    pub fn allow(mut self, pattern: impl ToString) -> Self {
+        self.allow.push(pattern.to_string());
+        self
+    }
+
+    /// Set what to do when problems are found. The default is [`RouteCheckMode::Warn`].
+    #[must_use]
+    pub fn mode(mut self, mode: RouteCheckMode) -> Self {
+        self.mode = mode;
+        self
+    }
+
+    /// Compare the (mounted) routes against the operations in the documentation.
+    pub fn report<'r>(&self, routes: impl IntoIterator<Item = &'r Route>) -> RouteReport {
+        let base = self.base.trim_end_matches('/');
+        let documented = self
+            .spec
+            .paths
+            .iter()
+            .flat_map(|(path, path_item)| {
+                path_item.operations().filter_map(move |(method, _)| {
+                    let method = method.parse::<Method>().ok()?;
+                    Some((method, format!("{}{}", base, path)))
+                })
+            })
+            .collect::<Vec<_>>();
+        let mounted = routes
+            .into_iter()
+            .filter(
+                |route| !matches!(&route.name, Some(name) if name.starts_with(ROUTE_NAME_PREFIX)),
+            )
+            .map(|route| (route.method, openapi_path(route.uri.path())))
+            .filter(|(_, path)| is_under(base, path))
+            .collect::<Vec<_>>();
+
+        let mut report = RouteReport::default();
+        for (method, path) in &mounted {
+            if !documented.iter().any(|op| is_same(op, (*method, path)))
+                && !self.is_allowed(*method, path)
+            {
+                report.undocumented.push((*method, path.clone()));
+            }
+        }
+        for (method, path) in &documented {
+            if !mounted.iter().any(|route| is_same(route, (*method, path)))
+                && !self.is_allowed(*method, path)
+            {
+                report.stale.push((*method, path.clone()));
+            }
+        }
+        report
+    }
+
+    fn is_allowed(&self, method: Method, path: &str) -> bool {
+        self.allow
+            .iter()
+            .any(|pattern| match pattern.split_once(' ') {
+                Some((allowed_method, pattern)) => {
+                    allowed_method.eq_ignore_ascii_case(method.as_str())
+                        && glob_matches(pattern.trim(), path)
+                }
+                None => glob_matches(pattern, path),
+            })
+    }
+}
+
+#[rocket::async_trait]
+impl Fairing for RouteCheck {
+    fn info(&self) -> fairing::Info {
+        fairing::Info {
+            name: "OpenAPI route check",
+            kind: Kind::Liftoff,
+        }
+    }
+
+    async fn on_liftoff(&self, rocket: &Rocket<Orbit>) {
+        let report = self.report(rocket.routes());
+        if report.is_empty() {
+            return;
+        }
+        match self.mode {
+            RouteCheckMode::Warn => log::warn!("{}", report),
+            RouteCheckMode::Fail => {
+                log::error!("{}", report);
+                rocket.shutdown().notify();
+            }
+        }
+    }
+}
+
+/// The result of a [`RouteCheck`].
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct RouteReport {
+    /// The method and (OpenAPI) path of the mounted routes that are not documented.
+    pub undocumented: Vec<(Method, String)>,
+    /// The method and path of the documented operations that have no mounted route.
+    pub stale: Vec<(Method, String)>,
+}
+
+impl RouteReport {
+    /// Returns `true` if all routes are documented and there are no stale operations.
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.undocumented.is_empty() && self.stale.is_empty()
+    }
+}
+
+impl fmt::Display for RouteReport {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "The OpenAPI documentation does not match the mounted routes."
+        )?;
+        if !self.undocumented.is_empty() {
+            write!(f, "\nRoutes that are not documented:")?;
+            for (method, path) in &self.undocumented {
+                write!(f, "\n- {} {}", method, path)?;
+            }
+        }
+        if !self.stale.is_empty() {
+            write!(f, "\nDocumented operations without a mounted route:")?;
+            for (method, path) in &self.stale {
+                write!(f, "\n- {} {}", method, path)?;
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Convert the path of a Rocket route to an OpenAPI path, for example `/users/<id>` to
+/// `/users/{id}`.
+fn openapi_path(path: &str) -> String {
+    path.replace('<', "{").replace("..>", "}").replace('>', "}")
+}
+
+/// Check if `path` is `base` or a path under `base`. `base` has no trailing slash.
+fn is_under(base: &str, path: &str) -> bool {
+    match path.strip_prefix(base) {
+        Some(rest) => rest.is_empty() || rest.starts_with('/') || base.is_empty(),
+        None => false,
+    }
+}
+
+/// Check if two operations have the same method and path. Trailing slashes and the names of
+/// path parameters are ignored.
+fn is_same(a: &(Method, String), (method, path): (Method, &str)) -> bool {
+    fn segments(path: &str) -> impl Iterator<Item = &str> {
+        path.split('/')
+            .filter(|segment| !segment.is_empty())
+            .map(|segment| {
+                if segment.starts_with('{') {
+                    "{}"
+                } else {
+                    segment
+                }
+            })
+    }
+    a.0 == method && segments(&a.1).eq(segments(path))
+}
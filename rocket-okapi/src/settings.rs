@@ -1,5 +1,11 @@
+use crate::route_check::RouteCheckMode;
+use okapi::merge::MergeOptions;
+use okapi::openapi3::{SecurityRequirement, SecurityScheme};
+use okapi::Map;
 use schemars::gen::SchemaSettings;
 use serde::{Deserialize, Serialize};
+use std::fmt;
+use std::sync::Arc;
 
 /// Settings which are used to customize the behavior of the `OpenApiGenerator`.
 #[derive(Debug, Clone)]
@@ -9,6 +15,47 @@ pub struct OpenApiSettings {
     /// The path to the json file that contains the API specification. Then default is
     /// `openapi.json`.
     pub json_path: String,
+    /// The path to the yaml file that contains the API specification. The default is
+    /// `openapi.yaml`. Set this to `None` to not serve the specification as a yaml file.
+    /// Requests to the `json_path` can also get the yaml specification, by requesting
+    /// `application/yaml` in the `Accept` header.
+    /// This is only used when the `yaml` feature is enabled.
+    pub yaml_path: Option<String>,
+    /// The version of the OpenAPI specification that is generated. The default is
+    /// [`OpenApiVersion::V3_0`].
+    pub openapi_version: OpenApiVersion,
+    /// Options used to merge the OpenAPI documentation of multiple mount points together,
+    /// for example in [`mount_endpoints_and_merged_docs!`](crate::mount_endpoints_and_merged_docs).
+    /// By default conflicting components are logged and the first one is used.
+    pub merge_options: MergeOptions,
+    /// Remove the components that are not used by any of the routes from the generated
+    /// OpenAPI documentation. See [`OpenApi::prune_unused_components`]. The default is `false`.
+    ///
+    /// [`OpenApi::prune_unused_components`]: okapi::openapi3::OpenApi::prune_unused_components
+    pub prune_unused_components: bool,
+    /// Security schemes that are always added to the components of the generated documentation.
+    /// This can be used to document authentication that is enforced by a fairing instead of a
+    /// request guard. The default is empty.
+    pub security_schemes: Map<String, SecurityScheme>,
+    /// The security requirements that apply to all operations, unless an operation overrides
+    /// them (for example with `#[openapi(security = "none")]`). The default is empty.
+    pub security: Vec<SecurityRequirement>,
+    /// What to do when multiple (ranked) routes have the same path and method.
+    /// The default is [`RankedRoutePolicy::Merge`].
+    pub ranked_routes: RankedRoutePolicy,
+    /// How the `operationId` of an operation is created from the path of its route function,
+    /// when it is not set with `#[openapi(operation_id = "...")]`.
+    /// The default is [`OperationIdStrategy::ModuleQualified`].
+    pub operation_id_strategy: OperationIdStrategy,
+    /// Check at liftoff that the documentation matches the mounted routes, see
+    /// [`RouteCheck`](crate::route_check::RouteCheck). This is used by
+    /// [`mount_endpoints_and_merged_docs!`](crate::mount_endpoints_and_merged_docs) and the
+    /// [`OpenApiFairing`](crate::fairing::OpenApiFairing). The default is `None`, which disables
+    /// the check.
+    pub route_check: Option<RouteCheckMode>,
+    /// The routes and operations that are ignored by the route check, see
+    /// [`RouteCheck::allow`](crate::route_check::RouteCheck::allow). The default is empty.
+    pub route_check_allow: Vec<String>,
 }
 
 impl Default for OpenApiSettings {
@@ -16,6 +63,16 @@ impl Default for OpenApiSettings {
         OpenApiSettings {
             schema_settings: SchemaSettings::openapi3(),
             json_path: "/openapi.json".to_owned(),
+            yaml_path: Some("/openapi.yaml".to_owned()),
+            openapi_version: OpenApiVersion::default(),
+            merge_options: MergeOptions::default(),
+            prune_unused_components: false,
+            security_schemes: Map::new(),
+            security: Vec::new(),
+            ranked_routes: RankedRoutePolicy::default(),
+            operation_id_strategy: OperationIdStrategy::default(),
+            route_check: None,
+            route_check_allow: Vec::new(),
         }
     }
 }
@@ -30,6 +87,86 @@ impl OpenApiSettings {
     }
 }
 
+/// The version of the OpenAPI specification that is generated.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum OpenApiVersion {
+    /// Generate an OpenAPI 3.0 document.
+    #[default]
+    V3_0,
+    /// Generate an OpenAPI 3.1 document.
+    /// The JSON Schemas use `type: [.., "null"]` instead of `nullable` and
+    /// `examples` arrays instead of `example`.
+    V3_1,
+}
+
+/// What to do when multiple routes have the same path and method, which Rocket allows for routes
+/// with a different `rank`, for example `#[get("/user", rank = 2)]`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum RankedRoutePolicy {
+    /// Merge the operations into one operation. Parameters that are not used by all routes are
+    /// optional, and the request bodies and responses of the routes are combined.
+    #[default]
+    Merge,
+    /// Return an error.
+    Error,
+    /// Only document the first route.
+    KeepFirst,
+    /// Only document the last route.
+    KeepLast,
+}
+
+/// How the `operationId` of an operation is created from the path of its route function,
+/// for example `users::get_all`.
+#[derive(Clone, Default)]
+pub enum OperationIdStrategy {
+    /// Only use the name of the function: `get_all`.
+    FunctionName,
+    /// Use the path of the function, separated by underscores: `users_get_all`.
+    #[default]
+    ModuleQualified,
+    /// Use the name of the function in camelCase: `getAll`.
+    CamelCase,
+    /// Use a custom function, which is called with the path of the function.
+    Custom(Arc<dyn Fn(&str) -> String + Send + Sync>),
+}
+
+impl OperationIdStrategy {
+    /// Create the `operationId` for the route function with the given path.
+    #[must_use]
+    pub fn operation_id(&self, fn_path: &str) -> String {
+        let fn_path = fn_path.trim_start_matches(':');
+        let fn_name = fn_path.rsplit("::").next().unwrap_or(fn_path);
+        match self {
+            OperationIdStrategy::FunctionName => fn_name.to_owned(),
+            OperationIdStrategy::ModuleQualified => fn_path.replace("::", "_"),
+            OperationIdStrategy::CamelCase => {
+                let mut parts = fn_name.split('_').filter(|part| !part.is_empty());
+                let mut operation_id = parts.next().unwrap_or_default().to_owned();
+                for part in parts {
+                    let mut chars = part.chars();
+                    if let Some(first) = chars.next() {
+                        operation_id.extend(first.to_uppercase());
+                        operation_id.push_str(chars.as_str());
+                    }
+                }
+                operation_id
+            }
+            OperationIdStrategy::Custom(f) => f(fn_path),
+        }
+    }
+}
+
+impl fmt::Debug for OperationIdStrategy {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            OperationIdStrategy::FunctionName => write!(f, "FunctionName"),
+            OperationIdStrategy::ModuleQualified => write!(f, "ModuleQualified"),
+            OperationIdStrategy::CamelCase => write!(f, "CamelCase"),
+            OperationIdStrategy::Custom(_) => write!(f, "Custom(..)"),
+        }
+    }
+}
+
 /// Contains a named url.
 #[derive(Debug, Clone, Deserialize, Serialize)]
 pub struct UrlObject {
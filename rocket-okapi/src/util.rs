@@ -1,5 +1,8 @@
-use crate::{OpenApiError, Result};
-use okapi::openapi3::{MediaType, RefOr, Response, Responses, SchemaObject};
+use crate::{OpenApiError, OpenApiErrorKind, Result};
+use okapi::openapi3::{
+    Example, ExampleValue, Header, MediaType, Object, Parameter, ParameterValue, RefOr,
+    RequestBody, Response, Responses, SchemaObject,
+};
 use okapi::Map;
 
 // FIXME this whole file is a huge mess...
@@ -123,6 +126,131 @@ pub fn set_content_type(responses: &mut Responses, content_type: impl ToString)
     Ok(())
 }
 
+/// Replaces the description of all responses with `description`.
+pub fn set_description(responses: &mut Responses, description: impl ToString) -> Result<()> {
+    for resp_refor in responses.responses.values_mut() {
+        ensure_not_ref(resp_refor)?.description = description.to_string();
+    }
+    Ok(())
+}
+
+/// Adds a header with the given `name`, `description` and `schema` to all responses.
+pub fn add_response_header(
+    responses: &mut Responses,
+    name: impl ToString,
+    description: Option<String>,
+    schema: SchemaObject,
+    required: bool,
+) -> Result<()> {
+    for resp_refor in responses.responses.values_mut() {
+        let header = Header {
+            description: description.clone(),
+            required,
+            deprecated: false,
+            allow_empty_value: false,
+            value: ParameterValue::Schema {
+                style: None,
+                explode: None,
+                allow_reserved: false,
+                schema: schema.clone(),
+                example: None,
+                examples: None,
+            },
+            extensions: Default::default(),
+        };
+        ensure_not_ref(resp_refor)?
+            .headers
+            .insert(name.to_string(), header.into());
+    }
+    Ok(())
+}
+
+/// Replaces the Content-Type of all responses that have content with `content_type`.
+/// Responses without any content (for example the `404` added by `Option<T>`) and `Ref`
+/// responses are left untouched.
+///
+/// This is used to apply the `format` of a route (for routes without a payload) to its responses.
+pub fn set_format_content_type(responses: &mut Responses, content_type: impl ToString) {
+    for resp_refor in responses.responses.values_mut() {
+        let response = match resp_refor {
+            RefOr::Object(response) if !response.content.is_empty() => response,
+            _ => continue,
+        };
+        let content = &mut response.content;
+        let mt = content.values().fold(MediaType::default(), |mt, mt2| {
+            accept_either_media_type(mt, mt2.clone())
+        });
+        content.clear();
+        content.insert(content_type.to_string(), mt);
+    }
+}
+
+/// Replaces the Content-Type of the `RequestBody` with `content_type`.
+///
+/// This is used to apply the `format` of a route (for routes with a payload) to its request body.
+/// Returns an error if none of the current Content-Types of the `RequestBody` can accept
+/// `content_type`, because the route would then never be able to read its data.
+pub fn set_request_body_content_type(
+    request_body: &mut RequestBody,
+    content_type: impl ToString,
+) -> Result<()> {
+    let content_type = content_type.to_string();
+    let content = &mut request_body.content;
+    if !content.is_empty()
+        && !content
+            .keys()
+            .any(|accepted| media_type_accepts(accepted, &content_type))
+    {
+        return Err(OpenApiError::from_kind(OpenApiErrorKind::ContentTypeConflict(format!(
+            "The route format `{}` conflicts with the content type(s) accepted by the data guard: `{}`.",
+            content_type,
+            content.keys().cloned().collect::<Vec<_>>().join("`, `"),
+        ))));
+    }
+    let mt = content.values().fold(MediaType::default(), |mt, mt2| {
+        accept_either_media_type(mt, mt2.clone())
+    });
+    content.clear();
+    content.insert(content_type, mt);
+    Ok(())
+}
+
+/// Checks if data documented with the `accepted` Content-Type can also be sent using the
+/// `requested` Content-Type.
+/// - `*/*` and `application/octet-stream` accept everything (raw data guards).
+/// - `*` matches any type or subtype.
+/// - Structured syntax suffixes are accepted, so `application/json` accepts
+///   `application/vnd.acme+json`.
+/// - `multipart/form-data` accepts `application/x-www-form-urlencoded`, as Rocket's `Form` does.
+fn media_type_accepts(accepted: &str, requested: &str) -> bool {
+    fn split(media_type: &str) -> (String, String) {
+        let essence = media_type.split(';').next().unwrap_or_default();
+        let mut parts = essence.splitn(2, '/');
+        let top = parts.next().unwrap_or_default().trim().to_ascii_lowercase();
+        let sub = parts.next().unwrap_or_default().trim().to_ascii_lowercase();
+        (top, sub)
+    }
+    let (accepted_top, accepted_sub) = split(accepted);
+    let (requested_top, requested_sub) = split(requested);
+
+    match (accepted_top.as_str(), accepted_sub.as_str()) {
+        ("*", "*") | ("application", "octet-stream") => return true,
+        ("multipart", "form-data")
+            if requested_top == "application" && requested_sub == "x-www-form-urlencoded" =>
+        {
+            return true
+        }
+        _ => {}
+    }
+    if accepted_top != requested_top && accepted_top != "*" && requested_top != "*" {
+        return false;
+    }
+    accepted_sub == requested_sub
+        || accepted_sub == "*"
+        || requested_sub == "*"
+        || requested_sub.ends_with(&format!("+{}", accepted_sub))
+}
+
 /// Adds a `Response` to a `Responses` object with the given status code, Content-Type and `SchemaObject`.
 pub fn add_schema_response(
     responses: &mut Responses,
@@ -137,6 +265,211 @@ pub fn add_schema_response(
     add_content_response(responses, status, content_type, media)
 }
 
+/// Adds a response with the given `status` code, or overrides the existing one.
+///
+/// The description is only replaced when `description` is given, a new response without
+/// description uses the reason phrase of the status code. When `content` is given, it replaces
+/// all content of the response.
+pub fn override_response(
+    responses: &mut Responses,
+    status: u16,
+    description: Option<String>,
+    content: Option<(&str, SchemaObject)>,
+) -> Result<()> {
+    let response = ensure_not_ref(ensure_status_code_exists(responses, status))?;
+    if let Some(description) = description {
+        response.description = description;
+    } else if response.description.is_empty() {
+        response.description = rocket::http::Status::new(status).reason_lossy().to_owned();
+    }
+    if let Some((content_type, schema)) = content {
+        response.content.clear();
+        let media = MediaType {
+            schema: Some(schema),
+            ..MediaType::default()
+        };
+        add_media_type(&mut response.content, content_type, media);
+    }
+    Ok(())
+}
+
+/// Sets the `description` and `example` of the parameters with the given `name`, when they are
+/// provided. Returns `false` if none of the `parameters` has this name.
+pub fn document_parameter(
+    parameters: &mut [RefOr<Parameter>],
+    name: &str,
+    description: Option<&str>,
+    example: Option<impl serde::Serialize>,
+) -> Result<bool> {
+    let example = example
+        .map(|example| {
+            serde_json::to_value(example).map_err(|err| {
+                OpenApiError::from_kind(OpenApiErrorKind::SchemaFailure(format!(
+                    "Could not serialize example of parameter `{}`: {}",
+                    name, err
+                )))
+            })
+        })
+        .transpose()?;
+    let mut found = false;
+    for parameter in parameters.iter_mut() {
+        let parameter = match parameter {
+            RefOr::Object(parameter) if parameter.name == name => parameter,
+            _ => continue,
+        };
+        found = true;
+        if let Some(description) = description {
+            parameter.description = Some(description.to_owned());
+        }
+        if let Some(example) = &example {
+            match &mut parameter.value {
+                ParameterValue::Schema {
+                    example: single,
+                    examples,
+                    ..
+                } => insert_example(single, examples, None, example.clone()),
+                ParameterValue::Content { content } => {
+                    for media in content.values_mut() {
+                        insert_example(
+                            &mut media.example,
+                            &mut media.examples,
+                            None,
+                            example.clone(),
+                        );
+                    }
+                }
+            }
+        }
+    }
+    Ok(found)
+}
+
+/// Adds an example to all media types of the request body.
+/// Named examples are added to `examples`, otherwise `example` is replaced. When both are used,
+/// the unnamed example is added to `examples` under the name `example`.
+pub fn add_request_body_example(
+    request_body: Option<&mut RefOr<RequestBody>>,
+    name: Option<&str>,
+    example: impl serde::Serialize,
+) -> Result<()> {
+    let request_body = match request_body {
+        Some(RefOr::Object(request_body)) => request_body,
+        Some(RefOr::Ref(_)) => {
+            return Err(OpenApiError::from_kind(OpenApiErrorKind::RefAlteration(
+                "Adding examples to Ref request bodies is not supported.".to_owned(),
+            )))
+        }
+        None => {
+            return Err(OpenApiError::new(
+                "Can not add an example to a route without request body.".to_owned(),
+            ))
+        }
+    };
+    add_example(&mut request_body.content, name, example)
+}
+
+/// Adds an example to all media types of the response with the given `status` code.
+/// Named examples are added to `examples`, otherwise `example` is replaced. When both are used,
+/// the unnamed example is added to `examples` under the name `example`.
+pub fn add_response_example(
+    responses: &mut Responses,
+    status: u16,
+    name: Option<&str>,
+    example: impl serde::Serialize,
+) -> Result<()> {
+    let response = match responses.responses.get_mut(&status.to_string()) {
+        Some(response) => ensure_not_ref(response)?,
+        None => {
+            return Err(OpenApiError::from_kind(OpenApiErrorKind::StatusConflict(
+                format!(
+                    "Can not add an example to the `{}` response, because it is not documented.",
+                    status
+                ),
+            )))
+        }
+    };
+    add_example(&mut response.content, name, example)
+}
+
+fn add_example(
+    content: &mut Map<String, MediaType>,
+    name: Option<&str>,
+    example: impl serde::Serialize,
+) -> Result<()> {
+    if content.is_empty() {
+        return Err(OpenApiError::new(
+            "Can not add an example to a request body or response without content.".to_owned(),
+        ));
+    }
+    let example = serde_json::to_value(example).map_err(|err| {
+        OpenApiError::from_kind(OpenApiErrorKind::SchemaFailure(format!(
+            "Could not serialize example: {}",
+            err
+        )))
+    })?;
+    for media in content.values_mut() {
+        insert_example(
+            &mut media.example,
+            &mut media.examples,
+            name,
+            example.clone(),
+        );
+    }
+    Ok(())
+}
+
+/// The name under which an unnamed example is stored in `examples`.
+const UNNAMED_EXAMPLE: &str = "example";
+
+/// Adds `value` to `examples` when it is named, otherwise `example` is replaced.
+/// `example` and `examples` are mutually exclusive, so once both would be set the unnamed
+/// example is moved into `examples` as well.
+fn insert_example(
+    example: &mut Option<serde_json::Value>,
+    examples: &mut Option<Map<String, Example>>,
+    name: Option<&str>,
+    value: serde_json::Value,
+) {
+    let named = |value| Example {
+        summary: None,
+        description: None,
+        value: ExampleValue::Value(value),
+        extensions: Default::default(),
+    };
+    match name {
+        None if examples.is_none() => *example = Some(value),
+        None => {
+            examples
+                .get_or_insert_with(Map::new)
+                .insert(UNNAMED_EXAMPLE.to_owned(), named(value));
+        }
+        Some(name) => {
+            let examples = examples.get_or_insert_with(Map::new);
+            if let Some(unnamed) = example.take() {
+                examples.insert(UNNAMED_EXAMPLE.to_owned(), named(unnamed));
+            }
+            examples.insert(name.to_owned(), named(value));
+        }
+    }
+}
+
+/// Adds (or replaces) the vendor extension `name` with the serialized `value`.
+pub fn add_extension(
+    extensions: &mut Object,
+    name: impl ToString,
+    value: impl serde::Serialize,
+) -> Result<()> {
+    let name = name.to_string();
+    let value = serde_json::to_value(value).map_err(|err| {
+        OpenApiError::from_kind(OpenApiErrorKind::SchemaFailure(format!(
+            "Could not serialize extension `{}`: {}",
+            name, err
+        )))
+    })?;
+    extensions.insert(name, value);
+    Ok(())
+}
+
 /// Merges the the two given `Responses`.
 pub fn produce_any_responses(r1: Responses, r2: Responses) -> Result<Responses> {
     let mut result = Responses {
@@ -157,11 +490,25 @@ pub fn produce_any_responses(r1: Responses, r2: Responses) -> Result<Responses>
     Ok(result)
 }
 
+/// Merges the two given `RequestBody`s, the request body is only required if both are required.
+pub fn produce_any_request_bodies(b1: RequestBody, b2: RequestBody) -> RequestBody {
+    let mut content = b1.content;
+    for (content_type, media) in b2.content {
+        add_media_type(&mut content, content_type, media);
+    }
+    RequestBody {
+        description: b1.description.or(b2.description),
+        content,
+        required: b1.required && b2.required,
+        extensions: extend(b1.extensions, b2.extensions),
+    }
+}
+
 fn ensure_not_ref(response: &mut RefOr<Response>) -> Result<&mut Response> {
     match response {
-        RefOr::Ref(_) => Err(OpenApiError::new(
+        RefOr::Ref(_) => Err(OpenApiError::from_kind(OpenApiErrorKind::RefAlteration(
             "Altering Ref responses is not supported.".to_owned(),
-        )),
+        ))),
         RefOr::Object(o) => Ok(o),
     }
 }
@@ -174,7 +521,7 @@ fn extend<A, E: Extend<A>>(mut a: E, b: impl IntoIterator<Item = A>) -> E {
 fn produce_either_response(r1: Response, r2: Response) -> Response {
     let description = if r1.description.is_empty() {
         r2.description
-    } else if r2.description.is_empty() {
+    } else if r2.description.is_empty() || r1.description == r2.description {
         r1.description
     } else {
         format!("{}\n{}", r1.description, r2.description)
@@ -213,6 +560,7 @@ fn accept_either_schema(
     s2: Option<SchemaObject>,
 ) -> Option<SchemaObject> {
     let (s1, s2) = match (s1, s2) {
+        (Some(s1), Some(s2)) if s1 == s2 => return Some(s1),
         (Some(s1), Some(s2)) => (s1, s2),
         (Some(s), None) | (None, Some(s)) => return Some(s),
         (None, None) => return None,
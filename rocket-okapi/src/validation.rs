@@ -0,0 +1,788 @@
+use okapi::openapi3::{
+    MediaType, OpenApi, Operation, Parameter, ParameterValue, PathItem, RefOr, RequestBody,
+};
+use okapi::Map;
+use rocket::data::Data;
+use rocket::fairing::{self, Fairing, Kind};
+use rocket::http::uri::Origin;
+use rocket::http::{ContentType, Method, Status};
+use rocket::route::{Handler, Outcome};
+use rocket::{Build, Request, Rocket, Route};
+use schemars::schema::{InstanceType, Schema, SchemaObject, SingleOrVec};
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use std::sync::Arc;
+
+/// The path the invalid requests are rewritten to, so they are answered by the
+/// `ProblemHandler` instead of the route.
+const PROBLEM_PATH: &str = "/__rocket_okapi/request-validation";
+
+/// The maximum number of bytes of the body that Rocket allows a fairing to look at.
+const PEEK_LIMIT: usize = 512;
+
+/// A fairing that validates incoming requests against the OpenAPI documentation, before they
+/// reach the route.
+///
+/// For the documented operations that match the method and path of a request, it checks:
+/// - that the required query, header and cookie parameters are present,
+/// - that the path and query parameters match their schema (for example that an integer is
+///   given),
+/// - that a required request body is present,
+/// - that a JSON body is valid JSON and matches its schema (including the schemas in
+///   `components`).
+///
+/// Invalid requests get a `application/problem+json` response ([RFC 7807]) that lists all
+/// [`ValidationError`]s, with status `422 Unprocessable Entity` if only the body did not match
+/// its schema, and `400 Bad Request` otherwise. Requests that do not match a documented
+/// operation are not changed.
+///
+/// Multiple operations can match a request, for example `/items/{id}` and `/items/{name}` of
+/// routes with a different rank. Rocket forwards a request to the next route when a guard fails,
+/// so a request is only rejected when it is invalid for all of them. The problem of the operation
+/// with the most static segments is returned.
+///
+/// Rocket only allows a fairing to look at the first 512 bytes of the body, so larger bodies
+/// are not validated against their schema. Validating them in a handler that wraps the route is
+/// not possible either, because the body can not be given to the route after it is read. A
+/// missing required body is always detected.
+///
+/// Example:
+/// ```rust,ignore
+/// let (routes, spec) = openapi_get_routes_spec![get_user, create_user];
+/// rocket::build()
+///     .mount("/api", routes)
+///     .attach(RequestValidator::new(spec).mount_at("/api"))
+/// ```
+///
+/// [RFC 7807]: https://datatracker.ietf.org/doc/html/rfc7807
+#[derive(Debug, Clone)]
+pub struct RequestValidator {
+    spec: Arc<OpenApi>,
+    base: String,
+}
+
+impl RequestValidator {
+    /// Create a fairing that validates requests against the given documentation.
+    #[must_use]
+    pub fn new(spec: OpenApi) -> Self {
+        RequestValidator {
+            spec: Arc::new(spec),
+            base: "/".to_owned(),
+        }
+    }
+
+    /// Set the base path the routes of the documentation are mounted on, the paths in the
+    /// documentation are relative to this path. The default is `/`.
+    #[must_use]
+    pub fn mount_at(mut self, base: impl ToString) -> Self {
+        self.base = base.to_string();
+        self
+    }
+
+    /// Validate a request. `body` is the (start of the) body, and `complete` is `true` if
+    /// `body` contains the whole body.
+    /// Returns `None` if the request is valid for one of the documented operations it matches,
+    /// or does not match a documented operation.
+    #[must_use]
+    pub fn validate(
+        &self,
+        req: &Request<'_>,
+        body: &[u8],
+        complete: bool,
+    ) -> Option<ValidationProblem> {
+        let matched = self.find_operations(req.method(), req.uri());
+        self.validate_operations(req, &matched, body, complete)
+    }
+
+    /// Validate a request against all documented operations it matches. Returns the problem of
+    /// the first operation if the request is invalid for all of them.
+    fn validate_operations(
+        &self,
+        req: &Request<'_>,
+        matched: &[MatchedOperation<'_>],
+        body: &[u8],
+        complete: bool,
+    ) -> Option<ValidationProblem> {
+        let mut first_problem = None;
+        for matched in matched {
+            match self.validate_operation(req, matched, body, complete) {
+                None => return None,
+                Some(problem) => {
+                    first_problem.get_or_insert(problem);
+                }
+            }
+        }
+        first_problem
+    }
+
+    /// Validate a request against the documented operation it matches.
+    fn validate_operation(
+        &self,
+        req: &Request<'_>,
+        matched: &MatchedOperation<'_>,
+        body: &[u8],
+        complete: bool,
+    ) -> Option<ValidationProblem> {
+        let MatchedOperation {
+            path_item,
+            operation,
+            path_values,
+        } = matched;
+        let mut errors = Vec::new();
+        for parameter in path_item.parameters.iter().chain(&operation.parameters) {
+            if let Some(parameter) = self.resolve_parameter(parameter) {
+                self.validate_parameter(req, parameter, path_values, &mut errors);
+            }
+        }
+        let mut body_is_invalid = false;
+        if let Some(request_body) = operation.request_body.as_ref() {
+            if let Some(request_body) = self.resolve_request_body(request_body) {
+                body_is_invalid =
+                    self.validate_body(req, request_body, body, complete, &mut errors);
+            }
+        }
+        if errors.is_empty() {
+            return None;
+        }
+        let status = if body_is_invalid && errors.iter().all(|err| err.location == "body") {
+            Status::UnprocessableEntity
+        } else {
+            Status::BadRequest
+        };
+        Some(ValidationProblem::new(status, errors))
+    }
+
+    /// Find the operations that match a request, and the values of their path parameters.
+    /// The operations are sorted by the number of static segments in their path (most first),
+    /// like Rocket prefers static segments over dynamic segments.
+    fn find_operations(&self, method: Method, uri: &Origin<'_>) -> Vec<MatchedOperation<'_>> {
+        let mut segments = uri.path().segments().collect::<Vec<_>>();
+        for base_segment in self.base.split('/').filter(|s| !s.is_empty()) {
+            if segments.first() != Some(&base_segment) {
+                return Vec::new();
+            }
+            segments.remove(0);
+        }
+
+        let mut matched: Vec<(usize, MatchedOperation<'_>)> = Vec::new();
+        for (path, path_item) in &self.spec.paths {
+            let operation = match method {
+                Method::Get | Method::Head => path_item.get.as_ref(),
+                Method::Put => path_item.put.as_ref(),
+                Method::Post => path_item.post.as_ref(),
+                Method::Delete => path_item.delete.as_ref(),
+                Method::Options => path_item.options.as_ref(),
+                Method::Patch => path_item.patch.as_ref(),
+                Method::Trace => path_item.trace.as_ref(),
+                _ => None,
+            };
+            let operation = match operation {
+                Some(operation) => operation,
+                None => continue,
+            };
+            let template = path
+                .split('/')
+                .filter(|s| !s.is_empty())
+                .collect::<Vec<_>>();
+            if template.len() != segments.len() {
+                continue;
+            }
+            let mut static_segments = 0;
+            let mut values = Vec::new();
+            let matches =
+                template.iter().zip(&segments).all(|(template, segment)| {
+                    match template.strip_prefix('{').and_then(|s| s.strip_suffix('}')) {
+                        Some(name) => {
+                            values.push((name.to_owned(), (*segment).to_owned()));
+                            true
+                        }
+                        None => {
+                            static_segments += 1;
+                            template == segment
+                        }
+                    }
+                });
+            if matches {
+                let operation = MatchedOperation {
+                    path_item,
+                    operation,
+                    path_values: values,
+                };
+                matched.push((static_segments, operation));
+            }
+        }
+        // A stable sort, so operations with the same number of static segments keep their order.
+        matched.sort_by(|(a, _), (b, _)| b.cmp(a));
+        matched
+            .into_iter()
+            .map(|(_, operation)| operation)
+            .collect()
+    }
+
+    fn validate_parameter(
+        &self,
+        req: &Request<'_>,
+        parameter: &Parameter,
+        path_values: &[(String, String)],
+        errors: &mut Vec<ValidationError>,
+    ) {
+        let name = parameter.name.as_str();
+        let values: Vec<String> = match parameter.location.as_str() {
+            "path" => path_values
+                .iter()
+                .filter(|(key, _)| key == name)
+                .map(|(_, value)| value.clone())
+                .collect(),
+            "query" => req
+                .uri()
+                .query()
+                .map(|query| {
+                    query
+                        .segments()
+                        .filter(|(key, _)| *key == name)
+                        .map(|(_, value)| value.to_owned())
+                        .collect()
+                })
+                .unwrap_or_default(),
+            "header" => req.headers().get(name).map(str::to_owned).collect(),
+            "cookie" => req
+                .cookies()
+                .get(name)
+                .map(|cookie| vec![cookie.value().to_owned()])
+                .unwrap_or_default(),
+            _ => return,
+        };
+        let error = |message: String| ValidationError {
+            location: parameter.location.clone(),
+            name: name.to_owned(),
+            message,
+        };
+        if values.is_empty() {
+            if parameter.required {
+                errors.push(error("Required parameter is missing.".to_owned()));
+            }
+            return;
+        }
+        let schema = match &parameter.value {
+            ParameterValue::Schema { schema, .. } => schema,
+            ParameterValue::Content { .. } => return,
+        };
+        // Only the path and query parameters are parsed by Rocket, so only their types are
+        // validated.
+        if !matches!(parameter.location.as_str(), "path" | "query") {
+            return;
+        }
+        let value = if self.allows_type(schema, InstanceType::Array) {
+            let items = schema
+                .array
+                .as_ref()
+                .and_then(|array| array.items.as_ref())
+                .and_then(|items| match items {
+                    SingleOrVec::Single(items) => Some(items.as_ref()),
+                    SingleOrVec::Vec(_) => None,
+                });
+            Value::Array(
+                values
+                    .iter()
+                    .map(|value| match items {
+                        Some(Schema::Object(items)) => self.parse_value(value, items),
+                        _ => Value::String(value.clone()),
+                    })
+                    .collect(),
+            )
+        } else {
+            self.parse_value(&values[0], schema)
+        };
+        let mut schema_errors = Vec::new();
+        self.validate_schema(&value, schema, "", &[], &mut schema_errors);
+        for (_, message) in schema_errors {
+            errors.push(error(message));
+        }
+    }
+
+    /// Validate the body. Returns `true` if the body is valid JSON, but does not match its
+    /// schema.
+    fn validate_body(
+        &self,
+        req: &Request<'_>,
+        request_body: &RequestBody,
+        body: &[u8],
+        complete: bool,
+        errors: &mut Vec<ValidationError>,
+    ) -> bool {
+        let error = |name: &str, message: String| ValidationError {
+            location: "body".to_owned(),
+            name: name.to_owned(),
+            message,
+        };
+        if body.is_empty() && complete {
+            if request_body.required {
+                errors.push(error("", "Required request body is missing.".to_owned()));
+            }
+            return false;
+        }
+        let is_json = matches!(req.content_type(), Some(content_type) if content_type.is_json());
+        let schema = match json_media_type(&request_body.content) {
+            Some(MediaType {
+                schema: Some(schema),
+                ..
+            }) if is_json => schema,
+            _ => return false,
+        };
+        if !complete {
+            log::debug!(
+                "The body of `{} {}` is larger than {} bytes, so it is not validated.",
+                req.method(),
+                req.uri(),
+                PEEK_LIMIT
+            );
+            return false;
+        }
+        let value = match serde_json::from_slice::<Value>(body) {
+            Ok(value) => value,
+            Err(err) => {
+                errors.push(error(
+                    "",
+                    format!("Request body is not valid JSON: {}", err),
+                ));
+                return false;
+            }
+        };
+        let mut schema_errors = Vec::new();
+        self.validate_schema(&value, schema, "", &[], &mut schema_errors);
+        let is_invalid = !schema_errors.is_empty();
+        for (pointer, message) in schema_errors {
+            errors.push(error(&pointer, message));
+        }
+        is_invalid
+    }
+
+    /// Validate a value against a schema. Every error is added as (JSON Pointer, message).
+    ///
+    /// `refs` are the references that were followed for this value, so a schema that
+    /// references itself (like `A: { allOf: [{ $ref: A }] }`) is reported instead of recursing
+    /// forever. Recursive schemas are fine, because they are validated against a nested value.
+    fn validate_schema(
+        &self,
+        value: &Value,
+        schema: &SchemaObject,
+        pointer: &str,
+        refs: &[&str],
+        errors: &mut Vec<(String, String)>,
+    ) {
+        let mut error = |message: String| errors.push((pointer.to_owned(), message));
+        if let Some(reference) = &schema.reference {
+            if refs.contains(&reference.as_str()) {
+                error(format!("The schema `{}` references itself.", reference));
+                return;
+            }
+            match self.resolve_schema(reference) {
+                Some(schema) => {
+                    let mut refs = refs.to_vec();
+                    refs.push(reference);
+                    self.validate_schema(value, schema, pointer, &refs, errors);
+                }
+                None => error(format!("Unknown schema `{}`.", reference)),
+            }
+            return;
+        }
+        if value.is_null() && schema.extensions.get("nullable") == Some(&Value::Bool(true)) {
+            return;
+        }
+        if let Some(instance_type) = &schema.instance_type {
+            let types: &[InstanceType] = match instance_type {
+                SingleOrVec::Single(instance_type) => std::slice::from_ref(instance_type),
+                SingleOrVec::Vec(types) => types,
+            };
+            if !types
+                .iter()
+                .any(|instance_type| has_type(value, instance_type))
+            {
+                error(format!(
+                    "Expected {}, found {}.",
+                    types.iter().map(type_name).collect::<Vec<_>>().join(" or "),
+                    value_type_name(value)
+                ));
+                return;
+            }
+        }
+        if let Some(enum_values) = &schema.enum_values {
+            if !enum_values.contains(value) {
+                error(format!(
+                    "Expected one of {}.",
+                    Value::Array(enum_values.clone())
+                ));
+            }
+        }
+        if let Some(const_value) = &schema.const_value {
+            if const_value != value {
+                error(format!("Expected {}.", const_value));
+            }
+        }
+        if let (Some(number), Some(n)) = (&schema.number, value.as_f64()) {
+            if let Some(minimum) = number.minimum.filter(|minimum| n < *minimum) {
+                error(format!("Must be at least {}.", minimum));
+            }
+            if let Some(maximum) = number.maximum.filter(|maximum| n > *maximum) {
+                error(format!("Must be at most {}.", maximum));
+            }
+            if let Some(minimum) = number.exclusive_minimum.filter(|minimum| n <= *minimum) {
+                error(format!("Must be greater than {}.", minimum));
+            }
+            if let Some(maximum) = number.exclusive_maximum.filter(|maximum| n >= *maximum) {
+                error(format!("Must be less than {}.", maximum));
+            }
+        }
+        if let (Some(string), Some(s)) = (&schema.string, value.as_str()) {
+            let length = s.chars().count() as u32;
+            if let Some(min_length) = string.min_length.filter(|min| length < *min) {
+                error(format!("Must be at least {} characters long.", min_length));
+            }
+            if let Some(max_length) = string.max_length.filter(|max| length > *max) {
+                error(format!("Must be at most {} characters long.", max_length));
+            }
+        }
+        if let (Some(array), Some(items)) = (&schema.array, value.as_array()) {
+            let length = items.len() as u32;
+            if let Some(min_items) = array.min_items.filter(|min| length < *min) {
+                error(format!("Must contain at least {} items.", min_items));
+            }
+            if let Some(max_items) = array.max_items.filter(|max| length > *max) {
+                error(format!("Must contain at most {} items.", max_items));
+            }
+            for (index, item) in items.iter().enumerate() {
+                let item_schema = match &array.items {
+                    Some(SingleOrVec::Single(schema)) => Some(schema.as_ref()),
+                    Some(SingleOrVec::Vec(schemas)) => schemas.get(index),
+                    None => None,
+                };
+                if let Some(item_schema) = item_schema {
+                    let pointer = format!("{}/{}", pointer, index);
+                    self.validate_subschema(item, item_schema, &pointer, &[], errors);
+                }
+            }
+        }
+        if let (Some(object), Some(properties)) = (&schema.object, value.as_object()) {
+            for required in &object.required {
+                if !properties.contains_key(required) {
+                    errors.push((
+                        format!("{}/{}", pointer, escape_pointer(required)),
+                        "Required property is missing.".to_owned(),
+                    ));
+                }
+            }
+            // The properties are validated in the order of the schema.
+            for (name, property) in &object.properties {
+                if let Some(value) = properties.get(name) {
+                    let pointer = format!("{}/{}", pointer, escape_pointer(name));
+                    self.validate_subschema(value, property, &pointer, &[], errors);
+                }
+            }
+            if let (Some(additional), true) = (
+                &object.additional_properties,
+                object.pattern_properties.is_empty(),
+            ) {
+                for (name, value) in properties {
+                    if !object.properties.contains_key(name) {
+                        let pointer = format!("{}/{}", pointer, escape_pointer(name));
+                        self.validate_subschema(value, additional, &pointer, &[], errors);
+                    }
+                }
+            }
+        }
+        if let Some(subschemas) = &schema.subschemas {
+            if let Some(all_of) = &subschemas.all_of {
+                for subschema in all_of {
+                    self.validate_subschema(value, subschema, pointer, refs, errors);
+                }
+            }
+            let valid_count = |schemas: &[Schema]| {
+                schemas
+                    .iter()
+                    .filter(|subschema| {
+                        let mut errors = Vec::new();
+                        self.validate_subschema(value, subschema, pointer, refs, &mut errors);
+                        errors.is_empty()
+                    })
+                    .count()
+            };
+            if let Some(any_of) = &subschemas.any_of {
+                if valid_count(any_of) == 0 {
+                    errors.push((
+                        pointer.to_owned(),
+                        "Does not match any of the allowed schemas.".to_owned(),
+                    ));
+                }
+            }
+            if let Some(one_of) = &subschemas.one_of {
+                if valid_count(one_of) != 1 {
+                    errors.push((
+                        pointer.to_owned(),
+                        "Does not match exactly one of the allowed schemas.".to_owned(),
+                    ));
+                }
+            }
+            if let Some(not) = &subschemas.not {
+                if valid_count(std::slice::from_ref(not.as_ref())) == 1 {
+                    errors.push((
+                        pointer.to_owned(),
+                        "Matches a schema that is not allowed.".to_owned(),
+                    ));
+                }
+            }
+        }
+    }
+
+    fn validate_subschema(
+        &self,
+        value: &Value,
+        schema: &Schema,
+        pointer: &str,
+        refs: &[&str],
+        errors: &mut Vec<(String, String)>,
+    ) {
+        match schema {
+            Schema::Bool(true) => {}
+            Schema::Bool(false) => errors.push((pointer.to_owned(), "Is not allowed.".to_owned())),
+            Schema::Object(schema) => self.validate_schema(value, schema, pointer, refs, errors),
+        }
+    }
+
+    /// Parse the value of a parameter according to the type of its schema. If it can not be
+    /// parsed, it is returned as a string, so validating it reports the expected type.
+    fn parse_value(&self, value: &str, schema: &SchemaObject) -> Value {
+        let parsed = if self.allows_type(schema, InstanceType::Integer) {
+            value
+                .parse::<i64>()
+                .map(Value::from)
+                .or_else(|_| value.parse::<u64>().map(Value::from))
+                .ok()
+        } else if self.allows_type(schema, InstanceType::Number) {
+            value.parse::<f64>().ok().map(Value::from)
+        } else if self.allows_type(schema, InstanceType::Boolean) {
+            value.parse::<bool>().ok().map(Value::from)
+        } else {
+            None
+        };
+        parsed.unwrap_or_else(|| Value::String(value.to_owned()))
+    }
+
+    /// Check if the schema (or the schema it references) allows the given type.
+    fn allows_type<'a>(
+        &'a self,
+        mut schema: &'a SchemaObject,
+        instance_type: InstanceType,
+    ) -> bool {
+        // The references that were followed, to stop at references that form a cycle.
+        let mut visited = Vec::new();
+        while let Some(reference) = &schema.reference {
+            if visited.contains(&reference) {
+                return false;
+            }
+            visited.push(reference);
+            schema = match self.resolve_schema(reference) {
+                Some(schema) => schema,
+                None => return false,
+            };
+        }
+        match &schema.instance_type {
+            Some(SingleOrVec::Single(single)) => **single == instance_type,
+            Some(SingleOrVec::Vec(types)) => types.contains(&instance_type),
+            None => false,
+        }
+    }
+
+    fn resolve_schema(&self, reference: &str) -> Option<&SchemaObject> {
+        let name = reference.strip_prefix("#/components/schemas/")?;
+        self.spec.components.as_ref()?.schemas.get(name)
+    }
+
+    fn resolve_parameter<'a>(&'a self, parameter: &'a RefOr<Parameter>) -> Option<&'a Parameter> {
+        match parameter {
+            RefOr::Object(parameter) => Some(parameter),
+            RefOr::Ref(reference) => {
+                let name = reference
+                    .reference
+                    .strip_prefix("#/components/parameters/")?;
+                match self.spec.components.as_ref()?.parameters.get(name)? {
+                    RefOr::Object(parameter) => Some(parameter),
+                    RefOr::Ref(_) => None,
+                }
+            }
+        }
+    }
+
+    fn resolve_request_body<'a>(
+        &'a self,
+        request_body: &'a RefOr<RequestBody>,
+    ) -> Option<&'a RequestBody> {
+        match request_body {
+            RefOr::Object(request_body) => Some(request_body),
+            RefOr::Ref(reference) => {
+                let name = reference
+                    .reference
+                    .strip_prefix("#/components/requestBodies/")?;
+                match self.spec.components.as_ref()?.request_bodies.get(name)? {
+                    RefOr::Object(request_body) => Some(request_body),
+                    RefOr::Ref(_) => None,
+                }
+            }
+        }
+    }
+}
+
+#[rocket::async_trait]
+impl Fairing for RequestValidator {
+    fn info(&self) -> fairing::Info {
+        fairing::Info {
+            name: "OpenAPI request validation",
+            kind: Kind::Ignite | Kind::Request,
+        }
+    }
+
+    async fn on_ignite(&self, rocket: Rocket<Build>) -> fairing::Result {
+        let mut route = Route::ranked(isize::MIN, Method::Get, PROBLEM_PATH, ProblemHandler);
+        // Named, so this route is not reported by the `RouteCheck`.
+        route.name = Some("rocket_okapi::validation".into());
+        Ok(rocket.mount("/", vec![route]))
+    }
+
+    async fn on_request(&self, req: &mut Request<'_>, data: &mut Data<'_>) {
+        let matched = self.find_operations(req.method(), req.uri());
+        if matched.is_empty() {
+            return;
+        }
+        // Only wait for the body if it is documented.
+        let has_body = matched
+            .iter()
+            .any(|matched| matched.operation.request_body.is_some());
+        let (body, complete) = if has_body {
+            (data.peek(PEEK_LIMIT).await.to_vec(), data.peek_complete())
+        } else {
+            (Vec::new(), false)
+        };
+        if let Some(problem) = self.validate_operations(req, &matched, &body, complete) {
+            req.local_cache(|| Some(problem));
+            req.set_method(Method::Get);
+            req.set_uri(Origin::parse(PROBLEM_PATH).unwrap());
+        }
+    }
+}
+
+/// A documented operation that matches a request.
+struct MatchedOperation<'a> {
+    path_item: &'a PathItem,
+    operation: &'a Operation,
+    /// The name and value of every path parameter.
+    path_values: Vec<(String, String)>,
+}
+
+/// Responds with the `ValidationProblem` of the request.
+#[derive(Clone)]
+struct ProblemHandler;
+
+#[rocket::async_trait]
+impl Handler for ProblemHandler {
+    async fn handle<'r>(&self, req: &'r Request<'_>, data: Data<'r>) -> Outcome<'r> {
+        match req.local_cache(|| None::<ValidationProblem>) {
+            Some(problem) => {
+                let status = Status::from_code(problem.status).unwrap_or(Status::BadRequest);
+                let json = serde_json::to_string(problem).unwrap_or_default();
+                let content_type = ContentType::new("application", "problem+json");
+                Outcome::from(req, (status, (content_type, json)))
+            }
+            None => Outcome::forward(data, Status::NotFound),
+        }
+    }
+}
+
+/// The body of the response to an invalid request, an `application/problem+json` document as
+/// described in [RFC 7807](https://datatracker.ietf.org/doc/html/rfc7807).
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize, Serialize)]
+pub struct ValidationProblem {
+    /// Always `about:blank`.
+    #[serde(rename = "type")]
+    pub problem_type: String,
+    /// The reason phrase of the status code.
+    pub title: String,
+    /// The status code of the response, `400` or `422`.
+    pub status: u16,
+    /// A description of the problem.
+    pub detail: String,
+    /// All problems that were found.
+    pub errors: Vec<ValidationError>,
+}
+
+impl ValidationProblem {
+    fn new(status: Status, errors: Vec<ValidationError>) -> Self {
+        ValidationProblem {
+            problem_type: "about:blank".to_owned(),
+            title: status.reason_lossy().to_owned(),
+            status: status.code,
+            detail: "The request does not match the OpenAPI documentation.".to_owned(),
+            errors,
+        }
+    }
+}
+
+/// A problem with a part of a request.
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize, Serialize)]
+pub struct ValidationError {
+    /// The location of the problem: `path`, `query`, `header`, `cookie` or `body`.
+    #[serde(rename = "in")]
+    pub location: String,
+    /// The name of the parameter, or the JSON Pointer to the invalid value in the body.
+    pub name: String,
+    /// A description of the problem.
+    pub message: String,
+}
+
+fn json_media_type(content: &Map<String, MediaType>) -> Option<&MediaType> {
+    content.iter().find_map(|(content_type, media_type)| {
+        let content_type = ContentType::parse_flexible(content_type)?;
+        content_type.is_json().then_some(media_type)
+    })
+}
+
+fn has_type(value: &Value, instance_type: &InstanceType) -> bool {
+    match instance_type {
+        InstanceType::Null => value.is_null(),
+        InstanceType::Boolean => value.is_boolean(),
+        InstanceType::Object => value.is_object(),
+        InstanceType::Array => value.is_array(),
+        InstanceType::Number => value.is_number(),
+        InstanceType::String => value.is_string(),
+        InstanceType::Integer => {
+            value.is_i64()
+                || value.is_u64()
+                || matches!(value.as_f64(), Some(n) if n.fract() == 0.0)
+        }
+    }
+}
+
+fn type_name(instance_type: &InstanceType) -> &'static str {
+    match instance_type {
+        InstanceType::Null => "null",
+        InstanceType::Boolean => "a boolean",
+        InstanceType::Object => "an object",
+        InstanceType::Array => "an array",
+        InstanceType::Number => "a number",
+        InstanceType::String => "a string",
+        InstanceType::Integer => "an integer",
+    }
+}
+
+fn value_type_name(value: &Value) -> &'static str {
+    match value {
+        Value::Null => "null",
+        Value::Bool(_) => "a boolean",
+        Value::Number(_) => "a number",
+        Value::String(_) => "a string",
+        Value::Array(_) => "an array",
+        Value::Object(_) => "an object",
+    }
+}
+
+fn escape_pointer(token: &str) -> String {
+    token.replace('~', "~0").replace('/', "~1")
+}
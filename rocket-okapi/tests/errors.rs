@@ -0,0 +1,85 @@
+//! This test ensures that errors contain the route that caused them, and that the errors of all
+//! routes are reported.
+
+use rocket::http::Method;
+use rocket_okapi::gen::OpenApiGenerator;
+use rocket_okapi::settings::OpenApiSettings;
+use rocket_okapi::{openapi_spec, OpenApiErrorKind, RouteContext};
+
+// These functions are never actually called.
+#[allow(unused)]
+mod endpoints {
+    use rocket::{get, post, serde::json::Json};
+    use rocket_okapi::openapi;
+
+    pub fn example() -> &'static str {
+        "example"
+    }
+
+    #[openapi]
+    #[post("/conflict", format = "text/html", data = "<body>")]
+    pub fn conflicting_body(body: Json<()>) -> Json<()> {
+        Json(())
+    }
+
+    #[openapi(response_example(status = 404, value = "example"))]
+    #[get("/users/<id>")]
+    pub fn get_user(id: u64) -> Json<()> {
+        Json(())
+    }
+
+    #[openapi]
+    #[get("/ok")]
+    pub fn ok() -> Json<()> {
+        Json(())
+    }
+}
+
+#[test]
+fn errors_contain_the_route() {
+    let mut gen = OpenApiGenerator::new(&OpenApiSettings::default());
+    let err =
+        endpoints::okapi_add_operation_for_get_user_(&mut gen, "get_user".to_owned()).unwrap_err();
+
+    assert!(matches!(err.kind(), OpenApiErrorKind::StatusConflict(_)));
+    assert_eq!(
+        err.route(),
+        Some(&RouteContext {
+            handler: "get_user".to_owned(),
+            method: Method::Get,
+            path: "/users/{id}".to_owned(),
+        })
+    );
+    assert_eq!(
+        err.to_string(),
+        "`get_user` (GET /users/{id}): Can not add an example to the `404` response, because it is not documented."
+    );
+
+    let err = endpoints::okapi_add_operation_for_conflicting_body_(
+        &mut gen,
+        "conflicting_body".to_owned(),
+    )
+    .unwrap_err();
+    assert!(matches!(
+        err.kind(),
+        OpenApiErrorKind::ContentTypeConflict(_)
+    ));
+    assert_eq!(err.route().unwrap().method, Method::Post);
+}
+
+#[test]
+fn errors_of_all_routes_are_collected() {
+    let result = std::panic::catch_unwind(|| {
+        openapi_spec![
+            endpoints::conflicting_body,
+            endpoints::ok,
+            endpoints::get_user
+        ](&OpenApiSettings::default())
+    });
+    let panic = result.unwrap_err();
+    let msg = panic.downcast_ref::<String>().unwrap();
+
+    assert!(msg.contains("2 errors occurred:"));
+    assert!(msg.contains("- `conflicting_body` (POST /conflict): The route format `text/html`"));
+    assert!(msg.contains("- `get_user` (GET /users/{id}): Can not add an example"));
+}
@@ -0,0 +1,114 @@
+//! This test ensures that examples can be added to the request body and responses by using
+//! `#[openapi(request_example = "...", response_example(...))]`.
+
+use rocket_okapi::okapi::openapi3::{ExampleValue, RefOr};
+use rocket_okapi::openapi_get_spec;
+use serde_json::json;
+
+// These functions are never actually called.
+#[allow(unused)]
+mod endpoints {
+    use rocket::{post, serde::json::Json};
+    use rocket_okapi::{openapi, JsonSchema};
+    use serde::{Deserialize, Serialize};
+
+    #[derive(Serialize, Deserialize, JsonSchema)]
+    pub struct User {
+        pub name: String,
+    }
+
+    pub mod examples {
+        use super::User;
+
+        pub fn new_user() -> User {
+            User {
+                name: "Alice".to_owned(),
+            }
+        }
+
+        pub fn admin() -> User {
+            User {
+                name: "root".to_owned(),
+            }
+        }
+
+        pub fn created() -> serde_json::Value {
+            serde_json::json!({ "name": "Alice" })
+        }
+    }
+
+    #[openapi(
+        request_example = "examples::new_user",
+        response_example(status = 200, value = "examples::created"),
+        response_example(status = 200, value = "examples::admin", name = "admin")
+    )]
+    #[post("/users", data = "<user>")]
+    pub fn create_user(user: Json<User>) -> Json<User> {
+        user
+    }
+
+    #[openapi(
+        request_example(value = "examples::new_user", name = "user"),
+        request_example(value = "examples::admin", name = "admin")
+    )]
+    #[post("/admins", data = "<user>")]
+    pub fn create_admin(user: Json<User>) {}
+}
+
+#[test]
+fn request_example_is_added() {
+    let spec = openapi_get_spec![endpoints::create_user];
+    let operation = spec.paths["/users"].post.as_ref().unwrap();
+
+    let request_body = match operation.request_body.as_ref().unwrap() {
+        RefOr::Object(request_body) => request_body,
+        RefOr::Ref(_) => panic!("Request body should not be a reference."),
+    };
+    let media = &request_body.content["application/json"];
+    assert_eq!(media.example, Some(json!({ "name": "Alice" })));
+}
+
+#[test]
+fn response_examples_are_added() {
+    let spec = openapi_get_spec![endpoints::create_user];
+    let operation = spec.paths["/users"].post.as_ref().unwrap();
+
+    let response = match &operation.responses.responses["200"] {
+        RefOr::Object(response) => response,
+        RefOr::Ref(_) => panic!("Response should not be a reference."),
+    };
+    let media = &response.content["application/json"];
+    // `example` and `examples` are mutually exclusive, so the unnamed example is moved.
+    assert_eq!(media.example, None);
+    let examples = media.examples.as_ref().unwrap();
+    assert_eq!(
+        examples["example"].value,
+        ExampleValue::Value(json!({ "name": "Alice" }))
+    );
+    assert_eq!(
+        examples["admin"].value,
+        ExampleValue::Value(json!({ "name": "root" }))
+    );
+}
+
+#[test]
+fn named_request_examples_are_added() {
+    let spec = openapi_get_spec![endpoints::create_admin];
+    let operation = spec.paths["/admins"].post.as_ref().unwrap();
+
+    let request_body = match operation.request_body.as_ref().unwrap() {
+        RefOr::Object(request_body) => request_body,
+        RefOr::Ref(_) => panic!("Request body should not be a reference."),
+    };
+    let media = &request_body.content["application/json"];
+    assert_eq!(media.example, None);
+    let mut names = media
+        .examples
+        .as_ref()
+        .unwrap()
+        .keys()
+        .cloned()
+        .collect::<Vec<_>>();
+    names.sort();
+    assert_eq!(names, ["admin", "user"]);
+}
@@ -0,0 +1,133 @@
+//! This test ensures that the documentation can be written to a file without launching Rocket,
+//! and that an out of date file is reported with a diff.
+
+use rocket_okapi::export;
+use rocket_okapi::settings::OpenApiSettings;
+use rocket_okapi::{mount_endpoints_and_merged_docs, openapi_get_routes_spec, openapi_get_spec};
+use std::path::PathBuf;
+
+// These functions are never actually called.
+#[allow(unused)]
+mod endpoints {
+    use rocket::{get, post, serde::json::Json};
+    use rocket_okapi::openapi;
+
+    /// Get a user.
+    #[openapi]
+    #[get("/users/<id>")]
+    pub fn get_user(id: u64) -> Json<String> {
+        Json(String::new())
+    }
+
+    #[openapi]
+    #[post("/users")]
+    pub fn create_user() -> Json<String> {
+        Json(String::new())
+    }
+}
+
+fn temp_file(name: &str) -> PathBuf {
+    let path = std::env::temp_dir().join(format!(
+        "rocket_okapi_export_{}_{}",
+        std::process::id(),
+        name
+    ));
+    let _ = std::fs::remove_file(&path);
+    path
+}
+
+#[test]
+fn write_and_check() {
+    let spec = openapi_get_spec![endpoints::get_user, endpoints::create_user];
+    let path = temp_file("write.json");
+    export::write(&spec, &path).unwrap();
+
+    let written: serde_json::Value =
+        serde_json::from_str(&std::fs::read_to_string(&path).unwrap()).unwrap();
+    assert_eq!(written, serde_json::to_value(&spec).unwrap());
+    export::check(&spec, &path).unwrap();
+    export::assert_up_to_date(&spec, &path);
+}
+
+#[test]
+fn check_reports_diff() {
+    let old = openapi_get_spec![endpoints::get_user, endpoints::create_user];
+    let path = temp_file("diff.json");
+    export::write(&old, &path).unwrap();
+
+    let mut new = openapi_get_spec![endpoints::get_user];
+    new.paths
+        .get_mut("/users/{id}")
+        .unwrap()
+        .get
+        .as_mut()
+        .unwrap()
+        .summary = Some("Get a user by id.".to_owned());
+    let err = export::check(&new, &path).unwrap_err().to_string();
+
+    assert!(err.contains("is out of date."), "{}", err);
+    assert!(
+        err.contains("- Breaking change at `/paths/~1users`: "),
+        "{}",
+        err
+    );
+    assert!(
+        err.contains("+        \"summary\": \"Get a user by id.\","),
+        "{}",
+        err
+    );
+    assert!(err.contains("\n-    \"/users\": {\n"), "{}", err);
+    assert!(err.contains("Run with `OPENAPI_UPDATE=1` to update the file."));
+}
+
+#[test]
+fn check_missing_file() {
+    let spec = openapi_get_spec![endpoints::get_user];
+    let path = temp_file("missing.json");
+    let err = export::check(&spec, &path).unwrap_err().to_string();
+    assert!(err.contains("Could not read"), "{}", err);
+}
+
+#[test]
+fn disabled_update_env_var_does_not_update() {
+    let spec = openapi_get_spec![endpoints::get_user];
+    let path = temp_file("disabled_update.json");
+    // Only values that disable updating are used, other tests in this file also read the variable.
+    for value in ["", "0", "false"] {
+        std::env::set_var(export::UPDATE_ENV_VAR, value);
+        let err = export::check(&spec, &path).unwrap_err().to_string();
+        assert!(err.contains("Could not read"), "{}: {}", value, err);
+    }
+    std::env::remove_var(export::UPDATE_ENV_VAR);
+    assert!(!path.exists());
+}
+
+#[test]
+fn spec_from_rocket() {
+    let settings = OpenApiSettings::default();
+    let mut rocket = rocket::build();
+    mount_endpoints_and_merged_docs! {
+        rocket, "/v1", settings,
+        "/api" => openapi_get_routes_spec![endpoints::get_user],
+    };
+    let spec = export::from_rocket(rocket, "/v1/openapi.json").unwrap();
+
+    assert_eq!(spec.servers[0].url, "/v1");
+    let paths = spec.paths.keys().cloned().collect::<Vec<_>>();
+    assert_eq!(paths, ["/api/users/{id}"]);
+
+    let err = export::from_rocket(rocket::build(), "/openapi.json").unwrap_err();
+    assert!(err.to_string().contains("404 Not Found"), "{}", err);
+}
+
+#[cfg(feature = "yaml")]
+#[test]
+fn write_and_check_yaml() {
+    let spec = openapi_get_spec![endpoints::get_user];
+    let path = temp_file("write.yaml");
+    export::write(&spec, &path).unwrap();
+
+    let yaml = std::fs::read_to_string(&path).unwrap();
+    assert!(yaml.starts_with("openapi: 3.0.0\n"), "{}", yaml);
+    export::check(&spec, &path).unwrap();
+}
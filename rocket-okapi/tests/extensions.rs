@@ -0,0 +1,80 @@
+//! This test ensures that vendor extensions and external docs can be added to an operation by
+//! using `#[openapi(extension(...), external_docs(...))]`.
+
+use rocket_okapi::okapi::openapi3::ExternalDocs;
+use rocket_okapi::openapi_get_spec;
+use serde_json::json;
+
+// These functions are never actually called.
+#[allow(unused)]
+mod endpoints {
+    use rocket::{get, serde::json::Json};
+    use rocket_okapi::openapi;
+
+    pub mod extensions {
+        pub fn integration() -> serde_json::Value {
+            serde_json::json!({ "type": "http_proxy", "httpMethod": "GET" })
+        }
+    }
+
+    #[openapi(
+        extension(name = "x-internal", value = true),
+        extension(name = "x-rate-limit", value = 100),
+        extension(name = "x-owner", value = "team-users"),
+        extension(
+            name = "x-amazon-apigateway-integration",
+            value_fn = "extensions::integration"
+        ),
+        external_docs(url = "https://example.com/users", description = "User guide")
+    )]
+    #[get("/users")]
+    pub fn users() -> Json<()> {
+        Json(())
+    }
+
+    #[openapi]
+    #[get("/default")]
+    pub fn default() -> Json<()> {
+        Json(())
+    }
+}
+
+#[test]
+fn extensions_are_added() {
+    let spec = openapi_get_spec![endpoints::users];
+    let operation = spec.paths["/users"].get.as_ref().unwrap();
+
+    assert_eq!(operation.extensions["x-internal"], json!(true));
+    assert_eq!(operation.extensions["x-rate-limit"], json!(100));
+    assert_eq!(operation.extensions["x-owner"], json!("team-users"));
+    assert_eq!(
+        operation.extensions["x-amazon-apigateway-integration"],
+        json!({ "type": "http_proxy", "httpMethod": "GET" })
+    );
+    let json = serde_json::to_value(operation).unwrap();
+    assert_eq!(json["x-internal"], json!(true));
+}
+
+#[test]
+fn external_docs_are_added() {
+    let spec = openapi_get_spec![endpoints::users];
+    let operation = spec.paths["/users"].get.as_ref().unwrap();
+
+    assert_eq!(
+        operation.external_docs,
+        Some(ExternalDocs {
+            url: "https://example.com/users".to_owned(),
+            description: Some("User guide".to_owned()),
+            ..Default::default()
+        })
+    );
+}
+
+#[test]
+fn default_has_no_extensions() {
+    let spec = openapi_get_spec![endpoints::default];
+    let operation = spec.paths["/default"].get.as_ref().unwrap();
+
+    assert!(operation.extensions.is_empty());
+    assert_eq!(operation.external_docs, None);
+}
@@ -0,0 +1,166 @@
+//! This test ensures that the `OpenApiFairing` documents the routes under the path they are
+//! mounted on.
+
+use rocket::http::Status;
+use rocket::local::blocking::Client;
+use rocket_okapi::fairing::OpenApiFairing;
+use rocket_okapi::okapi::openapi3::{Info, OpenApi};
+use rocket_okapi::openapi_operations;
+
+// These functions are never actually called.
+#[allow(unused)]
+mod endpoints {
+    use rocket::{get, post, serde::json::Json};
+    use rocket_okapi::{openapi, JsonSchema};
+    use serde::{Deserialize, Serialize};
+
+    #[derive(Serialize, Deserialize, JsonSchema)]
+    pub struct User {
+        pub name: String,
+    }
+
+    #[openapi]
+    #[get("/<id>")]
+    pub fn get_user(id: u64) -> Option<Json<User>> {
+        None
+    }
+
+    #[openapi]
+    #[post("/", data = "<user>")]
+    pub fn create_user(user: Json<User>) -> Json<User> {
+        user
+    }
+
+    #[openapi(skip)]
+    #[get("/internal")]
+    pub fn internal() -> &'static str {
+        ""
+    }
+
+    #[get("/health")]
+    pub fn health() -> &'static str {
+        ""
+    }
+}
+
+// Route functions with the same name and route attribute in different modules.
+#[allow(unused)]
+mod users {
+    use rocket::{get, serde::json::Json};
+    use rocket_okapi::openapi;
+
+    #[openapi]
+    #[get("/")]
+    pub fn get_all() -> Json<Vec<String>> {
+        Json(Vec::new())
+    }
+}
+
+#[allow(unused)]
+mod posts {
+    use rocket::{get, serde::json::Json};
+    use rocket_okapi::openapi;
+
+    #[openapi]
+    #[get("/")]
+    pub fn get_all() -> Json<Vec<u64>> {
+        Json(Vec::new())
+    }
+}
+
+fn fairing() -> OpenApiFairing {
+    OpenApiFairing::new(openapi_operations![
+        endpoints::get_user,
+        endpoints::create_user,
+        endpoints::internal,
+    ])
+    .info(Info {
+        title: "Users".to_owned(),
+        version: "1.0.0".to_owned(),
+        ..Default::default()
+    })
+}
+
+fn rocket() -> rocket::Rocket<rocket::Build> {
+    rocket::build()
+        .mount(
+            "/api/users",
+            rocket::routes![endpoints::get_user, endpoints::create_user],
+        )
+        .mount("/api/v2/users", rocket::routes![endpoints::get_user])
+        .mount(
+            "/api",
+            rocket::routes![endpoints::internal, endpoints::health],
+        )
+}
+
+#[test]
+fn fairing_documents_mounted_routes() {
+    let client = Client::tracked(rocket().attach(fairing())).unwrap();
+    let response = client.get("/openapi.json").dispatch();
+    assert_eq!(response.status(), Status::Ok);
+    let spec: OpenApi = response.into_json().unwrap();
+
+    assert_eq!(spec.info.title, "Users");
+    let paths = spec.paths.keys().cloned().collect::<Vec<_>>();
+    assert_eq!(
+        paths,
+        ["/api/users/{id}", "/api/users/", "/api/v2/users/{id}"]
+    );
+    assert_eq!(
+        spec.paths["/api/users/{id}"]
+            .get
+            .as_ref()
+            .unwrap()
+            .operation_id
+            .as_deref(),
+        Some("endpoints_get_user")
+    );
+}
+
+#[test]
+fn fairing_mount_at() {
+    let client = Client::tracked(rocket().attach(fairing().mount_at("/api"))).unwrap();
+    let spec: OpenApi = client
+        .get("/api/openapi.json")
+        .dispatch()
+        .into_json()
+        .unwrap();
+
+    assert_eq!(spec.servers[0].url, "/api");
+    assert!(spec.paths.contains_key("/users/{id}"));
+    assert!(spec.paths.contains_key("/v2/users/{id}"));
+}
+
+#[test]
+fn fairing_reports_undocumented_routes() {
+    let rocket = rocket();
+    let (_, undocumented) = fairing().generate(rocket.routes()).unwrap();
+    let undocumented = undocumented
+        .iter()
+        .map(|route| format!("{} {}", route.method, route.uri))
+        .collect::<Vec<_>>();
+    assert_eq!(undocumented, ["GET /api/health"]);
+}
+
+#[test]
+fn fairing_does_not_guess_ambiguous_routes() {
+    let rocket = rocket::build()
+        .mount("/users", rocket::routes![users::get_all])
+        .mount("/posts", rocket::routes![posts::get_all]);
+    let fairing = OpenApiFairing::new(openapi_operations![users::get_all, posts::get_all]);
+    let (spec, undocumented) = fairing.generate(rocket.routes()).unwrap();
+
+    assert!(spec.paths.is_empty());
+    let undocumented = undocumented
+        .iter()
+        .map(|route| format!("{} {}", route.method, route.uri))
+        .collect::<Vec<_>>();
+    assert_eq!(undocumented, ["GET /users", "GET /posts"]);
+
+    // Listing the same operation twice is not ambiguous.
+    let fairing = OpenApiFairing::new(openapi_operations![users::get_all, users::get_all]);
+    let (spec, undocumented) = fairing.generate(rocket.routes()).unwrap();
+    assert!(undocumented.is_empty());
+    assert_eq!(spec.paths.len(), 2);
+}
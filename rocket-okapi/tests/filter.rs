@@ -0,0 +1,68 @@
+//! This test ensures that the filtered specs are served at their own path.
+
+use rocket::local::blocking::Client;
+use rocket_okapi::okapi::filter::has_extension;
+use rocket_okapi::okapi::openapi3::OpenApi;
+use rocket_okapi::{get_filtered_openapi_routes, openapi_get_spec};
+
+// These functions are never actually called.
+#[allow(unused)]
+mod endpoints {
+    use rocket::{get, serde::json::Json};
+    use rocket_okapi::{openapi, JsonSchema};
+    use serde::Serialize;
+
+    #[derive(Serialize, JsonSchema)]
+    pub struct Metrics {
+        pub requests: u64,
+    }
+
+    #[openapi(tag = "Users")]
+    #[get("/users")]
+    pub fn get_users() -> Json<Vec<String>> {
+        Json(Vec::new())
+    }
+
+    #[openapi(tag = "Internal")]
+    #[get("/internal/metrics")]
+    pub fn get_metrics() -> Json<Metrics> {
+        Json(Metrics { requests: 0 })
+    }
+}
+
+fn spec() -> OpenApi {
+    let mut spec = openapi_get_spec![endpoints::get_users, endpoints::get_metrics];
+    let metrics = spec.paths.get_mut("/internal/metrics").unwrap();
+    metrics
+        .get
+        .as_mut()
+        .unwrap()
+        .extensions
+        .insert("x-internal".to_owned(), serde_json::json!(true));
+    spec
+}
+
+#[test]
+fn filtered_openapi_routes() {
+    let is_internal = has_extension("x-internal");
+    let routes = get_filtered_openapi_routes(
+        &spec(),
+        &[
+            ("/public.json", &|path, method, op| {
+                !is_internal(path, method, op)
+            }),
+            ("/internal.json", &is_internal),
+        ],
+    );
+    let client = Client::tracked(rocket::build().mount("/", routes)).unwrap();
+
+    let public: OpenApi = client.get("/public.json").dispatch().into_json().unwrap();
+    assert_eq!(public.paths.keys().collect::<Vec<_>>(), vec!["/users"]);
+    assert!(!public.components.unwrap().schemas.contains_key("Metrics"));
+
+    let internal: OpenApi = client.get("/internal.json").dispatch().into_json().unwrap();
+    assert_eq!(
+        internal.paths.keys().collect::<Vec<_>>(),
+        vec!["/internal/metrics"]
+    );
+}
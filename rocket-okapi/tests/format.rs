@@ -0,0 +1,115 @@
+//! This test ensures that the `format` of a route is used as the Content-Type in the documentation.
+
+use rocket_okapi::openapi_get_spec;
+
+/// A responder that documents its response as a reference to a component.
+pub struct RefResponse;
+
+impl<'r> rocket::response::Responder<'r, 'static> for RefResponse {
+    fn respond_to(self, _: &'r rocket::Request<'_>) -> rocket::response::Result<'static> {
+        Ok(rocket::Response::new())
+    }
+}
+
+impl rocket_okapi::response::OpenApiResponderInner for RefResponse {
+    fn responses(
+        _gen: &mut rocket_okapi::gen::OpenApiGenerator,
+    ) -> rocket_okapi::Result<rocket_okapi::okapi::openapi3::Responses> {
+        let mut responses = rocket_okapi::okapi::openapi3::Responses::default();
+        responses.responses.insert(
+            "200".to_owned(),
+            rocket_okapi::okapi::openapi3::RefOr::Ref(rocket_okapi::okapi::openapi3::Ref {
+                reference: "#/components/responses/Vendor".to_owned(),
+            }),
+        );
+        Ok(responses)
+    }
+}
+
+// These functions are never actually called.
+#[allow(unused)]
+mod endpoints {
+    use rocket::{get, post, serde::json::Json};
+    use rocket_okapi::openapi;
+
+    #[openapi]
+    #[post("/vendor", format = "application/vnd.acme+json", data = "<body>")]
+    pub fn vendor_body(body: Json<()>) -> Json<()> {
+        Json(())
+    }
+
+    #[openapi]
+    #[get("/vendor", format = "application/vnd.acme+json")]
+    pub fn vendor_response() -> Option<Json<()>> {
+        Some(Json(()))
+    }
+
+    #[openapi]
+    #[get("/reference", format = "application/vnd.acme+json")]
+    pub fn ref_response() -> super::RefResponse {
+        super::RefResponse
+    }
+
+    #[openapi]
+    #[post("/conflict", format = "text/html", data = "<body>")]
+    pub fn conflicting_body(body: Json<()>) -> Json<()> {
+        Json(())
+    }
+}
+
+#[test]
+fn format_replaces_request_body_content_type() {
+    let spec = openapi_get_spec![endpoints::vendor_body];
+
+    let operation = spec.paths["/vendor"].post.as_ref().unwrap();
+    let request_body = match operation.request_body.as_ref().unwrap() {
+        rocket_okapi::okapi::openapi3::RefOr::Object(body) => body,
+        rocket_okapi::okapi::openapi3::RefOr::Ref(_) => panic!("Request body should not be a ref."),
+    };
+    let content_types: Vec<_> = request_body.content.keys().collect();
+    assert_eq!(content_types, vec!["application/vnd.acme+json"]);
+    // For routes with a payload the format does not change the responses.
+    let response = match &operation.responses.responses["200"] {
+        rocket_okapi::okapi::openapi3::RefOr::Object(response) => response,
+        rocket_okapi::okapi::openapi3::RefOr::Ref(_) => panic!("Response should not be a ref."),
+    };
+    assert!(response.content.contains_key("application/json"));
+}
+
+#[test]
+fn format_replaces_response_content_type() {
+    let spec = openapi_get_spec![endpoints::vendor_response];
+
+    let operation = spec.paths["/vendor"].get.as_ref().unwrap();
+    let response = match &operation.responses.responses["200"] {
+        rocket_okapi::okapi::openapi3::RefOr::Object(response) => response,
+        rocket_okapi::okapi::openapi3::RefOr::Ref(_) => panic!("Response should not be a ref."),
+    };
+    let content_types: Vec<_> = response.content.keys().collect();
+    assert_eq!(content_types, vec!["application/vnd.acme+json"]);
+    // Responses without content do not get a Content-Type.
+    let not_found = match &operation.responses.responses["404"] {
+        rocket_okapi::okapi::openapi3::RefOr::Object(response) => response,
+        rocket_okapi::okapi::openapi3::RefOr::Ref(_) => panic!("Response should not be a ref."),
+    };
+    assert!(not_found.content.is_empty());
+}
+
+#[test]
+fn format_leaves_ref_responses_untouched() {
+    let spec = openapi_get_spec![endpoints::ref_response];
+
+    let operation = spec.paths["/reference"].get.as_ref().unwrap();
+    match &operation.responses.responses["200"] {
+        rocket_okapi::okapi::openapi3::RefOr::Ref(reference) => {
+            assert_eq!(reference.reference, "#/components/responses/Vendor");
+        }
+        rocket_okapi::okapi::openapi3::RefOr::Object(_) => panic!("Response should be a ref."),
+    }
+}
+
+#[test]
+#[should_panic(expected = "Could not generate OpenAPI operation")]
+fn format_conflicting_with_data_guard_fails() {
+    openapi_get_spec![endpoints::conflicting_body];
+}
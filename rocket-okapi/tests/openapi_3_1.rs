@@ -0,0 +1,119 @@
+//! This test ensures that an OpenAPI 3.1 document is generated when selected in the settings.
+
+use rocket_okapi::okapi::openapi3::{OpenApi, RefOr};
+use rocket_okapi::okapi::schemars::schema::{InstanceType, SingleOrVec};
+use rocket_okapi::openapi_get_spec;
+use rocket_okapi::settings::{OpenApiSettings, OpenApiVersion};
+
+// These functions are never actually called.
+#[allow(unused)]
+mod endpoints {
+    use rocket::{get, serde::json::Json};
+    use rocket_okapi::openapi;
+    use rocket_okapi::JsonSchema;
+    use serde::Serialize;
+
+    #[derive(Serialize, JsonSchema)]
+    pub struct Nested {
+        pub id: u64,
+    }
+
+    #[derive(Serialize, JsonSchema)]
+    pub struct User {
+        #[schemars(example = "example_name")]
+        pub name: Option<String>,
+        pub nested: Option<Nested>,
+    }
+
+    fn example_name() -> &'static str {
+        "bob"
+    }
+
+    #[openapi]
+    #[get("/user")]
+    pub fn get_user() -> Json<User> {
+        Json(User {
+            name: None,
+            nested: None,
+        })
+    }
+}
+
+fn user_schema(spec: &OpenApi) -> serde_json::Value {
+    let components = spec.components.as_ref().unwrap();
+    serde_json::to_value(&components.schemas["User"]).unwrap()
+}
+
+#[test]
+fn default_is_openapi_3_0() {
+    let spec = openapi_get_spec![endpoints::get_user];
+
+    assert_eq!(spec.openapi, "3.0.0");
+    let user = user_schema(&spec);
+    assert_eq!(user["properties"]["name"]["nullable"], true);
+    assert_eq!(user["properties"]["name"]["example"], "bob");
+}
+
+#[test]
+fn openapi_3_1_uses_json_schema_nullable_and_examples() {
+    let settings = OpenApiSettings {
+        openapi_version: OpenApiVersion::V3_1,
+        ..OpenApiSettings::default()
+    };
+    let spec = openapi_get_spec![settings: endpoints::get_user];
+
+    assert_eq!(spec.openapi, "3.1.0");
+    assert!(spec.is_v3_1());
+    assert_eq!(
+        spec.json_schema_dialect.as_deref(),
+        Some(rocket_okapi::okapi::v3_1::DEFAULT_JSON_SCHEMA_DIALECT)
+    );
+    let components = spec.components.as_ref().unwrap();
+    let name = match &components.schemas["User"]
+        .object
+        .as_ref()
+        .unwrap()
+        .properties["name"]
+    {
+        rocket_okapi::okapi::schemars::schema::Schema::Object(name) => name.clone(),
+        _ => panic!("Schema should be an object."),
+    };
+    assert_eq!(
+        name.instance_type,
+        Some(SingleOrVec::Vec(vec![
+            InstanceType::String,
+            InstanceType::Null
+        ]))
+    );
+    let user = user_schema(&spec);
+    assert!(user["properties"]["name"].get("nullable").is_none());
+    assert_eq!(
+        user["properties"]["name"]["examples"],
+        serde_json::json!(["bob"])
+    );
+    assert_eq!(
+        user["properties"]["nested"]["anyOf"][1],
+        serde_json::json!({ "type": "null" })
+    );
+}
+
+#[test]
+fn empty_spec_takes_version_when_merged() {
+    let settings = OpenApiSettings {
+        openapi_version: OpenApiVersion::V3_1,
+        ..OpenApiSettings::default()
+    };
+    let spec = openapi_get_spec![settings: endpoints::get_user];
+
+    let merged = rocket_okapi::okapi::merge::marge_spec_list(&[("/v1", spec)]).unwrap();
+    assert_eq!(merged.openapi, "3.1.0");
+    assert!(matches!(
+        merged.paths["/v1/user"]
+            .get
+            .as_ref()
+            .unwrap()
+            .responses
+            .responses["200"],
+        RefOr::Object(_)
+    ));
+}
@@ -0,0 +1,140 @@
+//! This test ensures that the `OpenApiHandler` caches responses and supports `ETag`s,
+//! minified output and compression.
+
+use rocket::http::{Header, Status};
+use rocket::local::blocking::Client;
+use rocket_okapi::handlers::OpenApiHandler;
+use rocket_okapi::okapi::openapi3::OpenApi;
+use rocket_okapi::openapi_get_routes;
+
+// These functions are never actually called.
+#[allow(unused)]
+mod endpoints {
+    use rocket::{get, serde::json::Json};
+    use rocket_okapi::openapi;
+
+    #[openapi]
+    #[get("/hello")]
+    pub fn hello() -> Json<String> {
+        Json("Hello".to_owned())
+    }
+}
+
+fn client() -> Client {
+    let rocket = rocket::build()
+        .mount("/v1", openapi_get_routes![endpoints::hello])
+        .mount("/v2", openapi_get_routes![endpoints::hello]);
+    Client::tracked(rocket).unwrap()
+}
+
+#[test]
+fn etag_and_if_none_match() {
+    let client = client();
+    let response = client.get("/v1/openapi.json").dispatch();
+    assert_eq!(response.status(), Status::Ok);
+    let etag = response.headers().get_one("ETag").unwrap().to_owned();
+    assert!(etag.starts_with('"') && etag.ends_with('"'));
+
+    let response = client
+        .get("/v1/openapi.json")
+        .header(Header::new("If-None-Match", etag.clone()))
+        .dispatch();
+    assert_eq!(response.status(), Status::NotModified);
+    assert_eq!(response.headers().get_one("ETag"), Some(etag.as_str()));
+    assert!(response.into_string().is_none());
+
+    // Every base path gets its own `servers`, so its own `ETag`.
+    let response = client
+        .get("/v2/openapi.json")
+        .header(Header::new("If-None-Match", etag))
+        .dispatch();
+    assert_eq!(response.status(), Status::Ok);
+    let spec: OpenApi = response.into_json().unwrap();
+    assert_eq!(spec.servers[0].url, "/v2");
+}
+
+#[test]
+fn etag_is_stable() {
+    let spec = OpenApi {
+        openapi: "3.0.0".to_owned(),
+        ..OpenApi::default()
+    };
+    let rocket = rocket::build().mount(
+        "/",
+        vec![OpenApiHandler::new(spec).into_route("/openapi.json")],
+    );
+    let client = Client::tracked(rocket).unwrap();
+    let response = client.get("/openapi.json?minified").dispatch();
+    let etag = response.headers().get_one("ETag").unwrap().to_owned();
+    assert_eq!(
+        response.into_string().unwrap(),
+        r#"{"openapi":"3.0.0","info":{"title":"","version":""},"paths":{}}"#
+    );
+    // The SHA-256 hash of the body.
+    assert_eq!(
+        etag,
+        "\"c77a688537e9c85a672ee9fe777b86fc2a91f8634639cc0f96f48ee5e214d037\""
+    );
+}
+
+#[test]
+fn minified() {
+    let client = client();
+    let pretty = client.get("/v1/openapi.json").dispatch();
+    let pretty_etag = pretty.headers().get_one("ETag").unwrap().to_owned();
+    let pretty = pretty.into_string().unwrap();
+    let minified = client.get("/v1/openapi.json?minified").dispatch();
+    assert_ne!(
+        minified.headers().get_one("ETag"),
+        Some(pretty_etag.as_str())
+    );
+    let minified = minified.into_string().unwrap();
+
+    assert!(pretty.contains('\n'));
+    assert!(!minified.contains('\n'));
+    let pretty: OpenApi = serde_json::from_str(&pretty).unwrap();
+    let minified: OpenApi = serde_json::from_str(&minified).unwrap();
+    assert_eq!(pretty, minified);
+}
+
+#[cfg(feature = "gzip")]
+#[test]
+fn gzip() {
+    use std::io::Read;
+
+    let client = client();
+    let response = client
+        .get("/v1/openapi.json")
+        .header(Header::new("Accept-Encoding", "gzip, deflate"))
+        .dispatch();
+    assert_eq!(response.headers().get_one("Content-Encoding"), Some("gzip"));
+    let body = response.into_bytes().unwrap();
+    let mut json = String::new();
+    flate2::read::GzDecoder::new(&body[..])
+        .read_to_string(&mut json)
+        .unwrap();
+    let spec: OpenApi = serde_json::from_str(&json).unwrap();
+    assert!(spec.paths.contains_key("/hello"));
+
+    let response = client
+        .get("/v1/openapi.json")
+        .header(Header::new("Accept-Encoding", "gzip;q=0"))
+        .dispatch();
+    assert_eq!(response.headers().get_one("Content-Encoding"), None);
+}
+
+#[cfg(feature = "brotli")]
+#[test]
+fn brotli() {
+    let client = client();
+    let response = client
+        .get("/v1/openapi.json")
+        .header(Header::new("Accept-Encoding", "gzip, br"))
+        .dispatch();
+    assert_eq!(response.headers().get_one("Content-Encoding"), Some("br"));
+    let body = response.into_bytes().unwrap();
+    let mut json = Vec::new();
+    brotli::BrotliDecompress(&mut &body[..], &mut json).unwrap();
+    let spec: OpenApi = serde_json::from_slice(&json).unwrap();
+    assert!(spec.paths.contains_key("/hello"));
+}
@@ -0,0 +1,127 @@
+//! This test ensures that the `operationId` is created using
+//! `OpenApiSettings::operation_id_strategy` and that duplicate ids are detected.
+
+use rocket::http::Method;
+use rocket_okapi::settings::{OpenApiSettings, OperationIdStrategy};
+use rocket_okapi::{openapi_get_spec, openapi_spec, DuplicateOperationId, OpenApiErrorKind};
+use std::sync::Arc;
+
+// These functions are never actually called.
+#[allow(unused)]
+mod users {
+    use rocket::{get, serde::json::Json};
+    use rocket_okapi::openapi;
+
+    #[openapi]
+    #[get("/users")]
+    pub fn get_all() -> Json<()> {
+        Json(())
+    }
+
+    #[openapi(operation_id = "listPosts")]
+    #[get("/users/posts")]
+    pub fn get_user_posts() -> Json<()> {
+        Json(())
+    }
+}
+
+#[allow(unused)]
+mod posts {
+    use rocket::{get, serde::json::Json};
+    use rocket_okapi::openapi;
+
+    #[openapi]
+    #[get("/posts")]
+    pub fn get_all() -> Json<()> {
+        Json(())
+    }
+
+    #[openapi(operation_id = "listPosts")]
+    #[get("/posts/all")]
+    pub fn list() -> Json<()> {
+        Json(())
+    }
+}
+
+fn operation_id(strategy: OperationIdStrategy) -> String {
+    let settings = OpenApiSettings {
+        operation_id_strategy: strategy,
+        ..OpenApiSettings::default()
+    };
+    let spec = openapi_get_spec![settings: users::get_user_posts, posts::get_all];
+    assert_eq!(
+        spec.paths["/users/posts"]
+            .get
+            .as_ref()
+            .unwrap()
+            .operation_id,
+        Some("listPosts".to_owned())
+    );
+    spec.paths["/posts"]
+        .get
+        .as_ref()
+        .unwrap()
+        .operation_id
+        .clone()
+        .unwrap()
+}
+
+#[test]
+fn operation_id_strategies() {
+    assert_eq!(
+        operation_id(OperationIdStrategy::default()),
+        "posts_get_all"
+    );
+    assert_eq!(operation_id(OperationIdStrategy::FunctionName), "get_all");
+    assert_eq!(operation_id(OperationIdStrategy::CamelCase), "getAll");
+    assert_eq!(
+        operation_id(OperationIdStrategy::Custom(Arc::new(|path| {
+            path.to_uppercase()
+        }))),
+        "POSTS::GET_ALL"
+    );
+}
+
+#[test]
+fn module_qualified_ids_are_unique() {
+    let spec = openapi_get_spec![users::get_all, posts::get_all];
+    assert_eq!(spec.paths.len(), 2);
+}
+
+fn duplicate(result: std::thread::Result<rocket_okapi::okapi::openapi3::OpenApi>) -> String {
+    let panic = result.unwrap_err();
+    panic.downcast_ref::<String>().cloned().unwrap_or_default()
+}
+
+#[test]
+fn duplicate_operation_ids_are_detected() {
+    let settings = OpenApiSettings {
+        operation_id_strategy: OperationIdStrategy::FunctionName,
+        ..OpenApiSettings::default()
+    };
+    let mut gen = rocket_okapi::gen::OpenApiGenerator::new(&settings);
+    users::okapi_add_operation_for_get_all_(&mut gen, "users::get_all".to_owned()).unwrap();
+    let err =
+        posts::okapi_add_operation_for_get_all_(&mut gen, "posts::get_all".to_owned()).unwrap_err();
+    match err.kind() {
+        OpenApiErrorKind::DuplicateOperationId(duplicate) => assert_eq!(
+            duplicate,
+            &DuplicateOperationId {
+                operation_id: "get_all".to_owned(),
+                first: (Method::Get, "/users".to_owned()),
+                second: (Method::Get, "/posts".to_owned()),
+            }
+        ),
+        kind => panic!("Expected a DuplicateOperationId error, got: {:?}", kind),
+    }
+    assert_eq!(
+        err.to_string(),
+        "`get_all` (GET /posts): The operationId `get_all` is used by both `GET /users` and `GET /posts`."
+    );
+
+    // Manually set ids are also checked.
+    let result = std::panic::catch_unwind(|| {
+        openapi_spec![users::get_user_posts, posts::list](&OpenApiSettings::default())
+    });
+    assert!(duplicate(result).contains("The operationId `listPosts` is used by both"));
+}
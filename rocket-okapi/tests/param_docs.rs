@@ -0,0 +1,121 @@
+//! This test ensures that parameters are documented using the `# Parameters` section of the
+//! doc comment and `#[openapi(param(...))]`.
+
+use rocket_okapi::okapi::openapi3::{Operation, Parameter, ParameterValue, RefOr};
+use rocket_okapi::openapi_get_spec;
+use serde_json::json;
+use std::collections::BTreeMap as Map;
+
+// These functions are never actually called.
+#[allow(unused)]
+mod endpoints {
+    use rocket::request::{self, FromRequest, Request};
+    use rocket::{get, serde::json::Json};
+    use rocket_okapi::gen::OpenApiGenerator;
+    use rocket_okapi::okapi::openapi3::{Object, Parameter, ParameterValue};
+    use rocket_okapi::openapi;
+    use rocket_okapi::request::{OpenApiFromRequest, RequestHeaderInput};
+
+    pub struct ApiKey;
+
+    #[rocket::async_trait]
+    impl<'a> FromRequest<'a> for ApiKey {
+        type Error = ();
+        async fn from_request(_request: &'a Request<'_>) -> request::Outcome<Self, Self::Error> {
+            request::Outcome::Success(ApiKey)
+        }
+    }
+
+    impl<'a> OpenApiFromRequest<'a> for ApiKey {
+        fn from_request_input(
+            gen: &mut OpenApiGenerator,
+            _name: String,
+            required: bool,
+        ) -> rocket_okapi::Result<RequestHeaderInput> {
+            Ok(RequestHeaderInput::Parameter(Parameter {
+                name: "x-api-key".to_owned(),
+                location: "header".to_owned(),
+                description: None,
+                required,
+                deprecated: false,
+                allow_empty_value: false,
+                value: ParameterValue::Schema {
+                    style: None,
+                    explode: None,
+                    allow_reserved: false,
+                    schema: gen.json_schema::<String>(),
+                    example: None,
+                    examples: None,
+                },
+                extensions: Object::default(),
+            }))
+        }
+    }
+
+    /// # Get a user
+    /// Returns the user with the given id.
+    ///
+    /// # Parameters
+    /// - `id`: The id of the user.
+    /// - `verbose`: Include all fields
+    ///   of the user.
+    /// - `x-api-key`: The key used to authenticate.
+    ///
+    /// # Errors
+    /// Returns nothing when the user does not exist.
+    #[openapi(
+        param(name = "id", example = 42),
+        param(name = "verbose", description = "Overridden.")
+    )]
+    #[get("/users/<id>?<verbose>")]
+    pub fn get_user(id: u64, verbose: Option<bool>, key: ApiKey) -> Option<Json<String>> {
+        None
+    }
+}
+
+fn parameters(operation: &Operation) -> Map<String, &Parameter> {
+    operation
+        .parameters
+        .iter()
+        .map(|parameter| match parameter {
+            RefOr::Object(parameter) => (parameter.name.clone(), parameter),
+            RefOr::Ref(_) => panic!("Parameters should not be references."),
+        })
+        .collect()
+}
+
+#[test]
+fn parameters_are_documented() {
+    let spec = openapi_get_spec![endpoints::get_user];
+    let operation = spec.paths["/users/{id}"].get.as_ref().unwrap();
+    let parameters = parameters(operation);
+
+    assert_eq!(
+        parameters["id"].description.as_deref(),
+        Some("The id of the user.")
+    );
+    match &parameters["id"].value {
+        ParameterValue::Schema { example, .. } => assert_eq!(example, &Some(json!(42))),
+        ParameterValue::Content { .. } => panic!("Parameter should have a schema."),
+    }
+    assert_eq!(
+        parameters["verbose"].description.as_deref(),
+        Some("Overridden.")
+    );
+    assert_eq!(
+        parameters["x-api-key"].description.as_deref(),
+        Some("The key used to authenticate.")
+    );
+}
+
+#[test]
+fn parameters_section_is_removed_from_description() {
+    let spec = openapi_get_spec![endpoints::get_user];
+    let operation = spec.paths["/users/{id}"].get.as_ref().unwrap();
+
+    assert_eq!(operation.summary.as_deref(), Some("Get a user"));
+    assert_eq!(
+        operation.description.as_deref(),
+        Some("Returns the user with the given id.\n\n# Errors Returns nothing when the user does not exist.")
+    );
+}
@@ -0,0 +1,32 @@
+//! This test ensures that unused components are removed from the generated documentation.
+
+use rocket_okapi::gen::OpenApiGenerator;
+use rocket_okapi::settings::OpenApiSettings;
+
+#[allow(unused)]
+mod models {
+    use rocket_okapi::JsonSchema;
+    use serde::Serialize;
+
+    #[derive(Serialize, JsonSchema)]
+    pub struct Address {
+        pub street: String,
+    }
+
+    #[derive(Serialize, JsonSchema)]
+    pub struct Unused {
+        pub address: Address,
+    }
+}
+
+#[test]
+fn prune_unused_components_setting() {
+    let settings = OpenApiSettings {
+        prune_unused_components: true,
+        ..Default::default()
+    };
+    let mut gen = OpenApiGenerator::new(&settings);
+    gen.json_schema::<models::Unused>();
+    let spec = gen.into_openapi();
+    assert!(spec.components.unwrap().schemas.is_empty());
+}
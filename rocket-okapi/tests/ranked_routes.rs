@@ -0,0 +1,245 @@
+//! This test ensures that ranked routes with the same path and method are documented according
+//! to `OpenApiSettings::ranked_routes`.
+
+use rocket_okapi::gen::OpenApiGenerator;
+use rocket_okapi::okapi::map;
+use rocket_okapi::okapi::openapi3::{Operation, RefOr, Response, Responses, SecurityRequirement};
+use rocket_okapi::settings::{OpenApiSettings, RankedRoutePolicy};
+use rocket_okapi::util::{add_schema_response, produce_any_responses};
+use rocket_okapi::{openapi_get_spec, openapi_spec};
+
+// These functions are never actually called.
+#[allow(unused)]
+mod endpoints {
+    use rocket::{get, post, serde::json::Json};
+    use rocket_okapi::{openapi, JsonSchema};
+    use serde::{Deserialize, Serialize};
+
+    #[derive(Serialize, Deserialize, JsonSchema)]
+    pub struct User {
+        pub name: String,
+    }
+
+    #[derive(Serialize, Deserialize, JsonSchema)]
+    pub struct Admin {
+        pub name: String,
+        pub level: u8,
+    }
+
+    /// # Get the user
+    #[openapi(tag = "Users")]
+    #[get("/user?<id>&<verbose>", rank = 1)]
+    pub fn get_user_by_id(id: u64, verbose: bool) -> Json<User> {
+        Json(User {
+            name: "Alice".to_owned(),
+        })
+    }
+
+    #[openapi(tag = "Users", tag = "Search")]
+    #[get("/user?<name>&<verbose>", rank = 2)]
+    pub fn get_user_by_name(name: String, verbose: bool) -> Option<Json<User>> {
+        None
+    }
+
+    #[openapi]
+    #[get("/admin?<id>", rank = 1)]
+    pub fn get_admin_by_id(id: u64) -> Json<Admin> {
+        Json(Admin {
+            name: "root".to_owned(),
+            level: 1,
+        })
+    }
+
+    #[openapi(security(name = "oauth", scopes("read:admins")))]
+    #[get("/admin?<name>", rank = 2)]
+    pub fn get_admin_by_name(name: String) -> Json<Admin> {
+        Json(Admin { name, level: 1 })
+    }
+
+    #[openapi(security = "none")]
+    #[get("/admin?<level>", rank = 3)]
+    pub fn get_admin_by_level(level: u8) -> Json<Admin> {
+        Json(Admin {
+            name: "root".to_owned(),
+            level,
+        })
+    }
+
+    #[openapi]
+    #[post("/user", data = "<user>", rank = 1)]
+    pub fn create_user(user: Json<User>) -> Json<User> {
+        user
+    }
+
+    #[openapi]
+    #[post("/user", data = "<admin>", rank = 2)]
+    pub fn create_admin(admin: Json<Admin>) -> Json<Admin> {
+        admin
+    }
+}
+
+fn required_parameters(operation: &Operation) -> Vec<(String, bool)> {
+    operation
+        .parameters
+        .iter()
+        .map(|parameter| match parameter {
+            RefOr::Object(parameter) => (parameter.name.clone(), parameter.required),
+            RefOr::Ref(_) => panic!("Parameters should not be references."),
+        })
+        .collect()
+}
+
+#[test]
+fn ranked_routes_are_merged() {
+    let spec = openapi_get_spec![endpoints::get_user_by_id, endpoints::get_user_by_name];
+    let operation = spec.paths["/user"].get.as_ref().unwrap();
+
+    assert_eq!(operation.summary.as_deref(), Some("Get the user"));
+    assert_eq!(operation.tags, ["Users", "Search"]);
+    assert_eq!(
+        required_parameters(operation),
+        [
+            ("id".to_owned(), false),
+            ("verbose".to_owned(), true),
+            ("name".to_owned(), false),
+        ]
+    );
+    let mut statuses = operation
+        .responses
+        .responses
+        .keys()
+        .cloned()
+        .collect::<Vec<_>>();
+    statuses.sort();
+    assert_eq!(statuses, ["200", "404"]);
+    // Both routes return a `User`, so the schema is not wrapped in an `anyOf`.
+    let response = match &operation.responses.responses["200"] {
+        RefOr::Object(response) => response,
+        RefOr::Ref(_) => panic!("Response should not be a reference."),
+    };
+    let schema = response.content["application/json"]
+        .schema
+        .as_ref()
+        .unwrap();
+    assert_eq!(
+        schema.reference.as_deref(),
+        Some("#/components/schemas/User")
+    );
+    assert!(schema.subschemas.is_none());
+}
+
+#[test]
+fn equal_responses_are_not_duplicated() {
+    let mut gen = OpenApiGenerator::new(&OpenApiSettings::default());
+    let mut responses = Responses::default();
+    responses.responses.insert(
+        "200".to_owned(),
+        RefOr::Object(Response {
+            description: "The user.".to_owned(),
+            ..Response::default()
+        }),
+    );
+    let mut user = responses;
+    add_schema_response(
+        &mut user,
+        200,
+        "application/json",
+        gen.json_schema::<endpoints::User>(),
+    )
+    .unwrap();
+
+    let merged = produce_any_responses(user.clone(), user.clone()).unwrap();
+    assert_eq!(merged, user);
+
+    let mut other = user.clone();
+    if let RefOr::Object(response) = other.responses.get_mut("200").unwrap() {
+        response.description = "The admin.".to_owned();
+    }
+    let merged = produce_any_responses(user, other).unwrap();
+    match &merged.responses["200"] {
+        RefOr::Object(response) => assert_eq!(response.description, "The admin.\nThe user."),
+        RefOr::Ref(_) => panic!("Response should not be a reference."),
+    }
+}
+
+#[test]
+fn ranked_request_bodies_are_merged() {
+    let spec = openapi_get_spec![endpoints::create_user, endpoints::create_admin];
+    let operation = spec.paths["/user"].post.as_ref().unwrap();
+
+    let request_body = match operation.request_body.as_ref().unwrap() {
+        RefOr::Object(request_body) => request_body,
+        RefOr::Ref(_) => panic!("Request body should not be a reference."),
+    };
+    assert!(request_body.required);
+    let schema = request_body.content["application/json"]
+        .schema
+        .clone()
+        .unwrap();
+    let any_of = schema.subschemas.unwrap().any_of.unwrap();
+    assert_eq!(any_of.len(), 2);
+}
+
+#[test]
+fn ranked_routes_keep_first_or_last() {
+    let mut settings = OpenApiSettings {
+        ranked_routes: RankedRoutePolicy::KeepFirst,
+        ..OpenApiSettings::default()
+    };
+    let spec = openapi_get_spec![settings: endpoints::get_user_by_id, endpoints::get_user_by_name];
+    let operation = spec.paths["/user"].get.as_ref().unwrap();
+    assert_eq!(
+        operation.operation_id.as_deref(),
+        Some("endpoints_get_user_by_id")
+    );
+
+    settings.ranked_routes = RankedRoutePolicy::KeepLast;
+    let spec = openapi_get_spec![settings: endpoints::get_user_by_id, endpoints::get_user_by_name];
+    let operation = spec.paths["/user"].get.as_ref().unwrap();
+    assert_eq!(
+        operation.operation_id.as_deref(),
+        Some("endpoints_get_user_by_name")
+    );
+}
+
+#[test]
+#[should_panic(expected = "Multiple routes are documented for `GET /user`.")]
+fn ranked_routes_error() {
+    let settings = OpenApiSettings {
+        ranked_routes: RankedRoutePolicy::Error,
+        ..OpenApiSettings::default()
+    };
+    let _ = openapi_spec![endpoints::get_user_by_id, endpoints::get_user_by_name](&settings);
+}
+
+#[test]
+fn ranked_routes_inherit_global_security() {
+    let api_key: SecurityRequirement = map! { "api_key".to_owned() => Vec::new() };
+    let oauth: SecurityRequirement = map! { "oauth".to_owned() => vec!["read:admins".to_owned()] };
+    let settings = OpenApiSettings {
+        security: vec![api_key.clone()],
+        ..OpenApiSettings::default()
+    };
+
+    // A route without its own security uses the global security, so it stays protected.
+    let spec =
+        openapi_get_spec![settings: endpoints::get_admin_by_id, endpoints::get_admin_by_name];
+    let operation = spec.paths["/admin"].get.as_ref().unwrap();
+    assert_eq!(
+        operation.security,
+        Some(vec![api_key.clone(), oauth.clone()])
+    );
+
+    // Only a route that is explicitly public makes the merged operation public.
+    let spec =
+        openapi_get_spec![settings: endpoints::get_admin_by_name, endpoints::get_admin_by_level];
+    let operation = spec.paths["/admin"].get.as_ref().unwrap();
+    assert_eq!(
+        operation.security,
+        Some(vec![oauth, SecurityRequirement::new()])
+    );
+
+    // Routes that both inherit the global security keep inheriting it.
+    let spec = openapi_get_spec![settings: endpoints::get_user_by_id, endpoints::get_user_by_name];
+    assert_eq!(spec.paths["/user"].get.as_ref().unwrap().security, None);
+}
@@ -0,0 +1,93 @@
+//! This test ensures that all routes documented with `#[openapi]` are added to the registry, so
+//! they can be documented without listing them.
+#![cfg(feature = "registry")]
+
+use rocket::local::blocking::Client;
+use rocket_okapi::fairing::OpenApiFairing;
+use rocket_okapi::gen::OpenApiGenerator;
+use rocket_okapi::okapi::openapi3::OpenApi;
+use rocket_okapi::registry;
+use rocket_okapi::settings::OpenApiSettings;
+
+// These functions are never actually called.
+#[allow(unused)]
+mod endpoints {
+    use rocket::{get, post, serde::json::Json};
+    use rocket_okapi::openapi;
+
+    pub mod users {
+        use super::*;
+
+        #[openapi]
+        #[get("/users")]
+        pub fn list() -> Json<Vec<String>> {
+            Json(Vec::new())
+        }
+
+        #[openapi]
+        #[post("/users", data = "<name>")]
+        pub fn create(name: Json<String>) -> Json<String> {
+            name
+        }
+    }
+
+    #[openapi]
+    #[get("/posts")]
+    pub fn posts() -> Json<Vec<String>> {
+        Json(Vec::new())
+    }
+
+    #[openapi(skip)]
+    #[get("/internal")]
+    pub fn internal() -> &'static str {
+        ""
+    }
+}
+
+#[test]
+fn operations_are_registered() {
+    let operations = registry::operations()
+        .iter()
+        .map(|operation| operation.fn_path())
+        .collect::<Vec<_>>();
+    assert_eq!(
+        operations,
+        [
+            "endpoints::internal",
+            "endpoints::posts",
+            "endpoints::users::create",
+            "endpoints::users::list",
+        ]
+    );
+}
+
+#[test]
+fn generator_adds_registered_operations() {
+    let mut gen = OpenApiGenerator::new(&OpenApiSettings::default());
+    gen.add_registered_operations().unwrap();
+    let spec = gen.into_openapi();
+
+    let paths = spec.paths.keys().cloned().collect::<Vec<_>>();
+    assert_eq!(paths, ["/posts", "/users"]);
+    let users = &spec.paths["/users"];
+    assert_eq!(
+        users.get.as_ref().unwrap().operation_id.as_deref(),
+        Some("endpoints_users_list")
+    );
+    assert!(users.post.is_some());
+}
+
+#[test]
+fn fairing_from_registry() {
+    let rocket = rocket::build()
+        .mount(
+            "/api",
+            rocket::routes![endpoints::users::list, endpoints::internal],
+        )
+        .attach(OpenApiFairing::from_registry());
+    let client = Client::tracked(rocket).unwrap();
+    let spec: OpenApi = client.get("/openapi.json").dispatch().into_json().unwrap();
+
+    let paths = spec.paths.keys().cloned().collect::<Vec<_>>();
+    assert_eq!(paths, ["/api/users"]);
+}
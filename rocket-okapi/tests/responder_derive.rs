@@ -0,0 +1,267 @@
+//! This test ensures that `#[derive(OpenApiResponder)]` documents every variant as a response.
+
+use rocket_okapi::okapi::openapi3::{ParameterValue, RefOr, Response};
+use rocket_okapi::okapi::schemars::schema::{InstanceType, SingleOrVec};
+use rocket_okapi::openapi_get_spec;
+
+// These functions are never actually called.
+#[allow(unused)]
+mod endpoints {
+    use rocket::http::{ContentType, Header};
+    use rocket::response::{self, Responder};
+    use rocket::serde::json::Json;
+    use rocket::{get, post, Request};
+    use rocket_okapi::gen::OpenApiGenerator;
+    use rocket_okapi::okapi::openapi3::SchemaObject;
+    use rocket_okapi::response::OpenApiHeader;
+    use rocket_okapi::{openapi, JsonSchema, OpenApiResponder};
+    use serde::Serialize;
+
+    #[derive(Serialize, JsonSchema)]
+    pub struct User {
+        pub name: String,
+    }
+
+    #[derive(Serialize, JsonSchema)]
+    pub struct ApiError {
+        pub message: String,
+    }
+
+    #[derive(rocket::Responder, OpenApiResponder)]
+    pub enum CreateUser {
+        /// The user was created.
+        #[response(status = 201)]
+        Created(Json<User>),
+        /// # Conflict
+        /// A user with this name already exists.
+        #[response(status = 409)]
+        Conflict(Json<ApiError>, ContentType),
+        #[response(status = 500, content_type = "plain")]
+        Error(String),
+    }
+
+    #[derive(rocket::Responder, OpenApiResponder)]
+    #[response(status = 202, content_type = "json")]
+    pub struct Accepted<'r>(&'r str);
+
+    /// A typed header, documented as an integer.
+    pub struct RateLimit(pub u32);
+
+    impl From<RateLimit> for Header<'static> {
+        fn from(rate_limit: RateLimit) -> Self {
+            Header::new("X-Rate-Limit-Remaining", rate_limit.0.to_string())
+        }
+    }
+
+    impl OpenApiHeader for RateLimit {
+        fn header_schema(gen: &mut OpenApiGenerator) -> SchemaObject {
+            gen.json_schema::<u32>()
+        }
+    }
+
+    #[derive(rocket::Responder, OpenApiResponder)]
+    #[response(status = 201)]
+    pub struct Created {
+        inner: Json<User>,
+        /// The url of the created user.
+        #[openapi_header(name = "Location")]
+        location: Header<'static>,
+        #[openapi_header(name = "ETag")]
+        etag: Header<'static>,
+        #[openapi_header(name = "X-Rate-Limit-Remaining")]
+        rate_limit: RateLimit,
+        #[openapi_header(skip)]
+        internal: Header<'static>,
+        content_type: ContentType,
+    }
+
+    /// Uses both Rocket's `#[response(ignore)]` and `#[openapi_header(...)]` on its fields.
+    #[derive(rocket::Responder, OpenApiResponder)]
+    #[response(status = 201)]
+    pub struct Copied {
+        inner: Json<User>,
+        #[response(ignore)]
+        #[openapi_header(name = "X-Source")]
+        source: String,
+        #[response(ignore)]
+        copies: u32,
+        #[openapi_header(name = "ETag")]
+        etag: Header<'static>,
+    }
+
+    #[derive(OpenApiResponder)]
+    pub struct Cached {
+        inner: Json<User>,
+        /// Only set when the user was found in the cache.
+        #[openapi_header(name = "Age")]
+        age: Option<Header<'static>>,
+    }
+
+    impl<'r> Responder<'r, 'static> for Cached {
+        fn respond_to(self, req: &'r Request<'_>) -> response::Result<'static> {
+            let mut response = self.inner.respond_to(req)?;
+            if let Some(age) = self.age {
+                response.set_header(age);
+            }
+            Ok(response)
+        }
+    }
+
+    #[openapi]
+    #[post("/users")]
+    pub fn create_user() -> CreateUser {
+        CreateUser::Error(String::new())
+    }
+
+    #[openapi]
+    #[post("/jobs")]
+    pub fn create_job() -> Accepted<'static> {
+        Accepted("{}")
+    }
+
+    #[openapi]
+    #[post("/users/v2")]
+    pub fn create_user_v2() -> Created {
+        Created {
+            inner: Json(User {
+                name: String::new(),
+            }),
+            location: Header::new("Location", "/users/1"),
+            etag: Header::new("ETag", "1"),
+            rate_limit: RateLimit(10),
+            internal: Header::new("X-Internal", ""),
+            content_type: ContentType::JSON,
+        }
+    }
+
+    #[openapi]
+    #[post("/users/copy")]
+    pub fn copy_user() -> Copied {
+        Copied {
+            inner: Json(User {
+                name: String::new(),
+            }),
+            source: String::new(),
+            copies: 1,
+            etag: Header::new("ETag", "1"),
+        }
+    }
+
+    #[openapi]
+    #[get("/users/cached")]
+    pub fn get_cached_user() -> Cached {
+        Cached {
+            inner: Json(User {
+                name: String::new(),
+            }),
+            age: None,
+        }
+    }
+}
+
+fn header<'a>(response: &'a Response, name: &str) -> &'a rocket_okapi::okapi::openapi3::Header {
+    match &response.headers[name] {
+        RefOr::Object(header) => header,
+        RefOr::Ref(_) => panic!("Header should not be a ref."),
+    }
+}
+
+fn header_type(header: &rocket_okapi::okapi::openapi3::Header) -> Option<InstanceType> {
+    match &header.value {
+        ParameterValue::Schema { schema, .. } => match &schema.instance_type {
+            Some(SingleOrVec::Single(ty)) => Some(**ty),
+            _ => None,
+        },
+        ParameterValue::Content { .. } => None,
+    }
+}
+
+fn response<'a>(
+    spec: &'a rocket_okapi::okapi::openapi3::OpenApi,
+    path: &str,
+    status: &str,
+) -> &'a Response {
+    let operation = spec.paths[path].post.as_ref().unwrap();
+    match &operation.responses.responses[status] {
+        RefOr::Object(response) => response,
+        RefOr::Ref(_) => panic!("Response should not be a ref."),
+    }
+}
+
+#[test]
+fn enum_variants_are_responses() {
+    let spec = openapi_get_spec![endpoints::create_user];
+    let statuses: Vec<_> = spec.paths["/users"]
+        .post
+        .as_ref()
+        .unwrap()
+        .responses
+        .responses
+        .keys()
+        .collect();
+    assert_eq!(statuses, vec!["201", "409", "500"]);
+
+    let created = response(&spec, "/users", "201");
+    assert_eq!(created.description, "The user was created.");
+    let schema = created.content["application/json"].schema.as_ref().unwrap();
+    assert_eq!(
+        schema.reference.as_deref(),
+        Some("#/components/schemas/User")
+    );
+
+    let conflict = response(&spec, "/users", "409");
+    assert_eq!(
+        conflict.description,
+        "Conflict\n\nA user with this name already exists."
+    );
+
+    let error = response(&spec, "/users", "500");
+    assert!(error.content.contains_key("text/plain; charset=utf-8"));
+}
+
+#[test]
+fn struct_responder() {
+    let spec = openapi_get_spec![endpoints::create_job];
+    let accepted = response(&spec, "/jobs", "202");
+    assert!(accepted.content.contains_key("application/json"));
+}
+
+#[test]
+fn header_fields() {
+    let spec = openapi_get_spec![endpoints::create_user_v2];
+    let created = response(&spec, "/users/v2", "201");
+    let headers: Vec<_> = created.headers.keys().collect();
+    assert_eq!(headers, vec!["Location", "ETag", "X-Rate-Limit-Remaining"]);
+
+    let location = header(created, "Location");
+    assert_eq!(
+        location.description.as_deref(),
+        Some("The url of the created user.")
+    );
+    assert!(location.required);
+    assert_eq!(header_type(location), Some(InstanceType::String));
+    // The schema of the header is provided by the type of the field.
+    let rate_limit = header(created, "X-Rate-Limit-Remaining");
+    assert_eq!(header_type(rate_limit), Some(InstanceType::Integer));
+}
+
+#[test]
+fn ignored_fields_are_not_headers() {
+    let spec = openapi_get_spec![endpoints::copy_user];
+    let created = response(&spec, "/users/copy", "201");
+    let headers: Vec<_> = created.headers.keys().collect();
+    assert_eq!(headers, vec!["ETag"]);
+}
+
+#[test]
+fn optional_header_fields_are_not_required() {
+    let spec = openapi_get_spec![endpoints::get_cached_user];
+    let operation = spec.paths["/users/cached"].get.as_ref().unwrap();
+    let ok = match &operation.responses.responses["200"] {
+        RefOr::Object(response) => response,
+        RefOr::Ref(_) => panic!("Response should not be a ref."),
+    };
+    let age = header(ok, "Age");
+    assert!(!age.required);
+    assert_eq!(header_type(age), Some(InstanceType::String));
+}
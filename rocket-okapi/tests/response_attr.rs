@@ -0,0 +1,92 @@
+//! This test ensures that responses can be added and overridden by using
+//! `#[openapi(response(...))]`.
+
+use rocket_okapi::okapi::openapi3::{Operation, RefOr, Response};
+use rocket_okapi::openapi_get_spec;
+
+// These functions are never actually called.
+#[allow(unused)]
+mod endpoints {
+    use rocket::{get, serde::json::Json};
+    use rocket_okapi::{openapi, JsonSchema};
+    use serde::Serialize;
+
+    #[derive(Serialize, JsonSchema)]
+    pub struct User {
+        pub name: String,
+    }
+
+    #[derive(Serialize, JsonSchema)]
+    pub struct ApiError {
+        pub message: String,
+    }
+
+    #[openapi(
+        response(status = 404, description = "User not found", body = "ApiError"),
+        response(status = 429)
+    )]
+    #[get("/users/<id>")]
+    pub fn get_user(id: u64) -> Option<Json<User>> {
+        None
+    }
+
+    #[openapi(response(
+        status = 200,
+        description = "The name of the user",
+        body = "String",
+        content_type = "text/plain"
+    ))]
+    #[get("/users/<id>/name")]
+    pub fn get_user_name(id: u64) -> Json<User> {
+        Json(User {
+            name: String::new(),
+        })
+    }
+}
+
+fn response<'a>(operation: &'a Operation, status: &str) -> &'a Response {
+    match &operation.responses.responses[status] {
+        RefOr::Object(response) => response,
+        RefOr::Ref(_) => panic!("Response `{}` should not be a reference.", status),
+    }
+}
+
+#[test]
+fn response_attribute_adds_responses() {
+    let spec = openapi_get_spec![endpoints::get_user];
+    let operation = spec.paths["/users/{id}"].get.as_ref().unwrap();
+
+    let mut statuses = operation
+        .responses
+        .responses
+        .keys()
+        .cloned()
+        .collect::<Vec<_>>();
+    statuses.sort();
+    assert_eq!(statuses, ["200", "404", "429"]);
+
+    let not_found = response(operation, "404");
+    assert_eq!(not_found.description, "User not found");
+    let schema = not_found.content["application/json"]
+        .schema
+        .as_ref()
+        .unwrap();
+    assert_eq!(
+        schema.reference.as_deref(),
+        Some("#/components/schemas/ApiError")
+    );
+
+    let too_many_requests = response(operation, "429");
+    assert_eq!(too_many_requests.description, "Too Many Requests");
+    assert!(too_many_requests.content.is_empty());
+}
+
+#[test]
+fn response_attribute_overrides_responses() {
+    let spec = openapi_get_spec![endpoints::get_user_name];
+    let operation = spec.paths["/users/{id}/name"].get.as_ref().unwrap();
+
+    let ok = response(operation, "200");
+    assert_eq!(ok.description, "The name of the user");
+    assert_eq!(ok.content.keys().collect::<Vec<_>>(), ["text/plain"]);
+}
@@ -0,0 +1,171 @@
+//! This test ensures that the `RouteCheck` reports mounted routes that are not documented and
+//! documented operations that are not mounted.
+
+use rocket::futures::FutureExt;
+use rocket::http::Method;
+use rocket::local::blocking::Client;
+use rocket::{Build, Rocket};
+use rocket_okapi::route_check::{RouteCheck, RouteCheckMode, RouteReport};
+use rocket_okapi::settings::OpenApiSettings;
+use rocket_okapi::{get_openapi_routes, mount_endpoints_and_merged_docs, openapi_get_routes_spec};
+
+// These functions are never actually called.
+#[allow(unused)]
+mod endpoints {
+    use rocket::{get, post, serde::json::Json};
+    use rocket_okapi::openapi;
+
+    #[openapi]
+    #[get("/users/<id>")]
+    pub fn get_user(id: u64) -> Json<()> {
+        Json(())
+    }
+
+    #[openapi]
+    #[post("/users")]
+    pub fn create_user() -> Json<()> {
+        Json(())
+    }
+
+    #[get("/health")]
+    pub fn health() -> &'static str {
+        ""
+    }
+
+    #[get("/metrics")]
+    pub fn metrics() -> &'static str {
+        ""
+    }
+}
+
+fn rocket() -> Rocket<Build> {
+    let (routes, _) = openapi_get_routes_spec![endpoints::get_user];
+    rocket::build()
+        .mount("/api", routes)
+        .mount(
+            "/api",
+            rocket::routes![endpoints::health, endpoints::metrics],
+        )
+        .mount("/", rocket::routes![endpoints::health])
+}
+
+fn check() -> RouteCheck {
+    let (_, spec) = openapi_get_routes_spec![endpoints::get_user, endpoints::create_user];
+    RouteCheck::new(spec).mount_at("/api")
+}
+
+#[test]
+fn report_lists_undocumented_and_stale_routes() {
+    let rocket = rocket();
+    let report = check().report(rocket.routes());
+    assert_eq!(
+        report,
+        RouteReport {
+            undocumented: vec![
+                (Method::Get, "/api/health".to_owned()),
+                (Method::Get, "/api/metrics".to_owned()),
+            ],
+            stale: vec![(Method::Post, "/api/users".to_owned())],
+        }
+    );
+    assert_eq!(
+        report.to_string(),
+        "The OpenAPI documentation does not match the mounted routes.\n\
+         Routes that are not documented:\n\
+         - GET /api/health\n\
+         - GET /api/metrics\n\
+         Documented operations without a mounted route:\n\
+         - POST /api/users"
+    );
+}
+
+#[test]
+fn allowed_routes_are_not_reported() {
+    let rocket = rocket();
+    let report = check()
+        .allow("GET /api/health")
+        .allow("/api/metrics")
+        .allow("POST /api/**")
+        .report(rocket.routes());
+    assert!(report.is_empty(), "{}", report);
+}
+
+#[test]
+fn documentation_routes_are_not_reported() {
+    let (_, spec) = openapi_get_routes_spec![endpoints::get_user];
+    let rocket = rocket().mount(
+        "/api",
+        get_openapi_routes(spec, &OpenApiSettings::default()),
+    );
+    let report = check()
+        .allow("/api/health")
+        .allow("/api/metrics")
+        .report(rocket.routes());
+    assert_eq!(report.undocumented, Vec::new());
+}
+
+#[test]
+fn fail_mode_shuts_down() {
+    let client = Client::tracked(rocket().attach(check().mode(RouteCheckMode::Fail))).unwrap();
+    assert!(client.rocket().shutdown().now_or_never().is_some());
+
+    let client = Client::tracked(rocket().attach(check())).unwrap();
+    assert!(client.rocket().shutdown().now_or_never().is_none());
+}
+
+#[test]
+fn route_check_from_settings() {
+    let settings = OpenApiSettings {
+        route_check: Some(RouteCheckMode::Fail),
+        route_check_allow: vec!["/api/metrics".to_owned()],
+        ..OpenApiSettings::default()
+    };
+    let mut rocket = rocket::build().mount("/api", rocket::routes![endpoints::metrics]);
+    mount_endpoints_and_merged_docs! {
+        rocket, "/api", settings,
+        "/v1" => openapi_get_routes_spec![endpoints::get_user],
+    };
+    let client = Client::tracked(rocket).unwrap();
+    assert!(client.rocket().shutdown().now_or_never().is_none());
+
+    let settings = OpenApiSettings {
+        route_check: Some(RouteCheckMode::Fail),
+        ..OpenApiSettings::default()
+    };
+    let mut rocket = rocket::build().mount("/api", rocket::routes![endpoints::metrics]);
+    mount_endpoints_and_merged_docs! {
+        rocket, "/api", settings,
+        "/v1" => openapi_get_routes_spec![endpoints::get_user],
+    };
+    let client = Client::tracked(rocket).unwrap();
+    assert!(client.rocket().shutdown().now_or_never().is_some());
+}
+
+#[test]
+fn only_routes_under_the_base_are_checked() {
+    let (_, spec) = openapi_get_routes_spec![endpoints::get_user];
+    let rocket = rocket::build()
+        .mount("/v1", rocket::routes![endpoints::get_user])
+        .mount("/v2", rocket::routes![endpoints::create_user])
+        .mount("/v10", rocket::routes![endpoints::health]);
+    let report = RouteCheck::new(spec)
+        .mount_at("/v1")
+        .report(rocket.routes());
+    assert!(report.is_empty(), "{}", report);
+
+    let settings = OpenApiSettings {
+        route_check: Some(RouteCheckMode::Fail),
+        ..OpenApiSettings::default()
+    };
+    let mut rocket = rocket::build();
+    mount_endpoints_and_merged_docs! {
+        rocket, "/v1", settings,
+        "/" => openapi_get_routes_spec![endpoints::get_user],
+    };
+    mount_endpoints_and_merged_docs! {
+        rocket, "/v2", settings,
+        "/" => openapi_get_routes_spec![endpoints::create_user],
+    };
+    let client = Client::tracked(rocket).unwrap();
+    assert!(client.rocket().shutdown().now_or_never().is_none());
+}
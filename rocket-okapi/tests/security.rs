@@ -0,0 +1,95 @@
+//! This test ensures that the security requirements of an operation can be set with
+//! `#[openapi(security ...)]` and that global defaults are used from `OpenApiSettings`.
+
+use rocket_okapi::okapi::openapi3::{SecurityRequirement, SecurityScheme, SecuritySchemeData};
+use rocket_okapi::okapi::{map, Map};
+use rocket_okapi::openapi_get_spec;
+use rocket_okapi::settings::OpenApiSettings;
+
+// These functions are never actually called.
+#[allow(unused)]
+mod endpoints {
+    use rocket::{get, serde::json::Json};
+    use rocket_okapi::openapi;
+
+    #[openapi(security = "none")]
+    #[get("/health")]
+    pub fn health() -> Json<()> {
+        Json(())
+    }
+
+    #[openapi(
+        security(name = "oauth", scopes("read:users", "write:users")),
+        security(name = "api_key")
+    )]
+    #[get("/users")]
+    pub fn users() -> Json<()> {
+        Json(())
+    }
+
+    #[openapi]
+    #[get("/default")]
+    pub fn default() -> Json<()> {
+        Json(())
+    }
+}
+
+#[test]
+fn security_none_is_public() {
+    let spec = openapi_get_spec![endpoints::health];
+
+    let operation = spec.paths["/health"].get.as_ref().unwrap();
+    assert_eq!(operation.security, Some(Vec::new()));
+    let json = serde_json::to_value(operation).unwrap();
+    assert_eq!(json["security"], serde_json::json!([]));
+}
+
+#[test]
+fn security_requirements_are_added() {
+    let spec = openapi_get_spec![endpoints::users];
+
+    let operation = spec.paths["/users"].get.as_ref().unwrap();
+    assert_eq!(
+        operation.security,
+        Some(vec![
+            map! { "oauth".to_owned() => vec!["read:users".to_owned(), "write:users".to_owned()] },
+            map! { "api_key".to_owned() => Vec::new() },
+        ])
+    );
+}
+
+#[test]
+fn default_has_no_security() {
+    let spec = openapi_get_spec![endpoints::default];
+
+    let operation = spec.paths["/default"].get.as_ref().unwrap();
+    assert_eq!(operation.security, None);
+    assert!(spec.security.is_empty());
+}
+
+#[test]
+fn global_security_from_settings() {
+    let scheme = SecurityScheme {
+        description: None,
+        data: SecuritySchemeData::ApiKey {
+            name: "x-api-key".to_owned(),
+            location: "header".to_owned(),
+        },
+        extensions: Map::new(),
+    };
+    let requirement: SecurityRequirement = map! { "api_key".to_owned() => Vec::new() };
+    let settings = OpenApiSettings {
+        security_schemes: map! { "api_key".to_owned() => scheme.clone() },
+        security: vec![requirement.clone()],
+        ..OpenApiSettings::default()
+    };
+    let spec = openapi_get_spec![settings: endpoints::default, endpoints::health];
+
+    assert_eq!(spec.security, vec![requirement]);
+    let components = spec.components.unwrap();
+    assert_eq!(components.security_schemes["api_key"], scheme.into());
+    assert_eq!(
+        spec.paths["/health"].get.as_ref().unwrap().security,
+        Some(Vec::new())
+    );
+}
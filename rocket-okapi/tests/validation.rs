@@ -0,0 +1,279 @@
+//! This test ensures that the `RequestValidator` rejects requests that do not match the
+//! documentation, before they reach the route.
+
+use rocket::http::{ContentType, Header, Status};
+use rocket::local::blocking::{Client, LocalResponse};
+use rocket_okapi::okapi::openapi3::{OpenApi, ParameterValue, RefOr};
+use rocket_okapi::okapi::schemars::schema::SchemaObject;
+use rocket_okapi::openapi_get_routes_spec;
+use rocket_okapi::validation::{RequestValidator, ValidationError, ValidationProblem};
+
+#[allow(unused)]
+mod endpoints {
+    use rocket::request::{self, FromRequest, Request};
+    use rocket::{get, post, serde::json::Json};
+    use rocket_okapi::gen::OpenApiGenerator;
+    use rocket_okapi::okapi::openapi3::{Object, Parameter, ParameterValue};
+    use rocket_okapi::request::{OpenApiFromRequest, RequestHeaderInput};
+    use rocket_okapi::{openapi, JsonSchema};
+    use serde::{Deserialize, Serialize};
+
+    /// A guard that documents a required header, but does not check it.
+    pub struct Auth;
+
+    #[rocket::async_trait]
+    impl<'a> FromRequest<'a> for Auth {
+        type Error = ();
+        async fn from_request(_request: &'a Request<'_>) -> request::Outcome<Self, Self::Error> {
+            request::Outcome::Success(Auth)
+        }
+    }
+
+    fn parameter(gen: &mut OpenApiGenerator, name: &str, location: &str) -> Parameter {
+        Parameter {
+            name: name.to_owned(),
+            location: location.to_owned(),
+            description: None,
+            required: true,
+            deprecated: false,
+            allow_empty_value: false,
+            value: ParameterValue::Schema {
+                style: None,
+                explode: None,
+                allow_reserved: false,
+                schema: gen.json_schema::<String>(),
+                example: None,
+                examples: None,
+            },
+            extensions: Object::default(),
+        }
+    }
+
+    impl<'a> OpenApiFromRequest<'a> for Auth {
+        fn from_request_input(
+            gen: &mut OpenApiGenerator,
+            _name: String,
+            _required: bool,
+        ) -> rocket_okapi::Result<RequestHeaderInput> {
+            Ok(RequestHeaderInput::Parameter(parameter(
+                gen,
+                "x-api-key",
+                "header",
+            )))
+        }
+    }
+
+    #[derive(Serialize, Deserialize, JsonSchema)]
+    pub struct NewUser {
+        pub name: String,
+        pub age: u8,
+        pub tags: Vec<String>,
+    }
+
+    #[openapi]
+    #[get("/users/<id>?<page>&<search>")]
+    pub fn get_user(id: u64, page: u32, search: Option<String>) -> String {
+        format!("user {}", id)
+    }
+
+    #[openapi]
+    #[get("/users/me")]
+    pub fn me(auth: Auth) -> &'static str {
+        "me"
+    }
+
+    #[openapi]
+    #[get("/items/<id>")]
+    pub fn get_item(id: u32) -> String {
+        format!("item {}", id)
+    }
+
+    #[openapi]
+    #[get("/items/<name>", rank = 2)]
+    pub fn get_item_by_name(name: String) -> String {
+        format!("item named {}", name)
+    }
+
+    #[openapi]
+    #[post("/users", data = "<user>")]
+    pub fn create_user(user: Json<NewUser>) -> Json<NewUser> {
+        user
+    }
+}
+
+fn client() -> Client {
+    let (routes, spec) =
+        openapi_get_routes_spec![endpoints::get_user, endpoints::me, endpoints::create_user];
+    client_with(routes, spec)
+}
+
+fn client_with(routes: Vec<rocket::Route>, spec: OpenApi) -> Client {
+    let rocket = rocket::build()
+        .mount("/api", routes)
+        .attach(RequestValidator::new(spec).mount_at("/api"));
+    Client::tracked(rocket).unwrap()
+}
+
+fn into_problem(response: LocalResponse<'_>) -> ValidationProblem {
+    assert_eq!(
+        response.content_type(),
+        Some(ContentType::new("application", "problem+json"))
+    );
+    response.into_json().unwrap()
+}
+
+fn error(location: &str, name: &str, message: &str) -> ValidationError {
+    ValidationError {
+        location: location.to_owned(),
+        name: name.to_owned(),
+        message: message.to_owned(),
+    }
+}
+
+#[test]
+fn valid_requests_reach_the_route() {
+    let client = client();
+    let response = client.get("/api/users/1?page=2").dispatch();
+    assert_eq!(response.status(), Status::Ok);
+    assert_eq!(response.into_string().unwrap(), "user 1");
+
+    let response = client
+        .get("/api/users/me")
+        .header(Header::new("x-api-key", "secret"))
+        .dispatch();
+    assert_eq!(response.status(), Status::Ok);
+
+    let response = client
+        .post("/api/users")
+        .header(ContentType::JSON)
+        .body(r#"{"name": "Alice", "age": 30, "tags": ["admin"]}"#)
+        .dispatch();
+    assert_eq!(response.status(), Status::Ok);
+}
+
+#[test]
+fn invalid_parameters_are_rejected() {
+    let client = client();
+    let response = client.get("/api/users/abc").dispatch();
+    assert_eq!(response.status(), Status::BadRequest);
+    let problem = into_problem(response);
+    assert_eq!(problem.status, 400);
+    assert_eq!(problem.title, "Bad Request");
+    assert_eq!(
+        problem.errors,
+        [
+            error("path", "id", "Expected an integer, found a string."),
+            error("query", "page", "Required parameter is missing."),
+        ]
+    );
+
+    let response = client.get("/api/users/1?page=-1").dispatch();
+    assert_eq!(
+        into_problem(response).errors,
+        [error("query", "page", "Must be at least 0.")]
+    );
+}
+
+#[test]
+fn undocumented_guard_requirements_are_rejected() {
+    let client = client();
+    let response = client.get("/api/users/me").dispatch();
+    assert_eq!(response.status(), Status::BadRequest);
+    assert_eq!(
+        into_problem(response).errors,
+        [error(
+            "header",
+            "x-api-key",
+            "Required parameter is missing."
+        )]
+    );
+}
+
+#[test]
+fn invalid_bodies_are_rejected() {
+    let client = client();
+    let response = client
+        .post("/api/users")
+        .header(ContentType::JSON)
+        .body(r#"{"name": 1, "age": -1, "tags": [true]}"#)
+        .dispatch();
+    assert_eq!(response.status(), Status::UnprocessableEntity);
+    let problem = into_problem(response);
+    assert_eq!(problem.status, 422);
+    assert_eq!(
+        problem.errors,
+        [
+            error("body", "/name", "Expected a string, found a number."),
+            error("body", "/age", "Must be at least 0."),
+            error("body", "/tags/0", "Expected a string, found a boolean."),
+        ]
+    );
+
+    let response = client
+        .post("/api/users")
+        .header(ContentType::JSON)
+        .body(r#"{"name": "Alice""#)
+        .dispatch();
+    assert_eq!(response.status(), Status::BadRequest);
+
+    let response = client
+        .post("/api/users")
+        .header(ContentType::JSON)
+        .dispatch();
+    assert_eq!(response.status(), Status::BadRequest);
+    assert_eq!(
+        into_problem(response).errors,
+        [error("body", "", "Required request body is missing.")]
+    );
+}
+
+#[test]
+fn schemas_that_reference_themselves_are_reported() {
+    let (routes, mut spec) = openapi_get_routes_spec![endpoints::get_user];
+    let schemas = &mut spec.components.get_or_insert_with(Default::default).schemas;
+    for (name, other) in [("A", "B"), ("B", "A")] {
+        schemas.insert(
+            name.to_owned(),
+            SchemaObject::new_ref(format!("#/components/schemas/{}", other)),
+        );
+    }
+    let operation = spec.paths["/users/{id}"].get.as_mut().unwrap();
+    for parameter in &mut operation.parameters {
+        match parameter {
+            RefOr::Object(parameter) if parameter.name == "page" => match &mut parameter.value {
+                ParameterValue::Schema { schema, .. } => {
+                    *schema = SchemaObject::new_ref("#/components/schemas/A".to_owned())
+                }
+                ParameterValue::Content { .. } => panic!("Parameter should have a schema."),
+            },
+            _ => {}
+        }
+    }
+
+    let client = client_with(routes, spec);
+    let response = client.get("/api/users/1?page=2").dispatch();
+    assert_eq!(response.status(), Status::BadRequest);
+    assert_eq!(
+        into_problem(response).errors,
+        [error(
+            "query",
+            "page",
+            "The schema `#/components/schemas/A` references itself."
+        )]
+    );
+}
+
+#[test]
+fn requests_valid_for_a_ranked_route_are_accepted() {
+    let (routes, spec) = openapi_get_routes_spec![endpoints::get_item, endpoints::get_item_by_name];
+    let client = client_with(routes, spec);
+
+    let response = client.get("/api/items/1").dispatch();
+    assert_eq!(response.into_string().unwrap(), "item 1");
+    // Not a `u32`, so Rocket forwards the request to the route with rank 2.
+    let response = client.get("/api/items/box").dispatch();
+    assert_eq!(response.status(), Status::Ok);
+    assert_eq!(response.into_string().unwrap(), "item named box");
+    let response = client.get("/api/items/-1").dispatch();
+    assert_eq!(response.into_string().unwrap(), "item named -1");
+}
@@ -0,0 +1,59 @@
+//! This test ensures that the documentation can be served as YAML.
+#![cfg(feature = "yaml")]
+
+use rocket::http::{Accept, ContentType, MediaType};
+use rocket::local::blocking::Client;
+use rocket_okapi::okapi::openapi3::OpenApi;
+use rocket_okapi::openapi_get_routes;
+
+// These functions are never actually called.
+#[allow(unused)]
+mod endpoints {
+    use rocket::{get, serde::json::Json};
+    use rocket_okapi::openapi;
+
+    #[openapi]
+    #[get("/hello")]
+    pub fn hello() -> Json<String> {
+        Json("Hello".to_owned())
+    }
+}
+
+fn client() -> Client {
+    let rocket = rocket::build().mount("/api", openapi_get_routes![endpoints::hello]);
+    Client::tracked(rocket).unwrap()
+}
+
+#[test]
+fn serve_yaml_path() {
+    let client = client();
+    let response = client.get("/api/openapi.yaml").dispatch();
+    assert_eq!(
+        response.content_type(),
+        Some(ContentType::new("application", "yaml"))
+    );
+    let spec = OpenApi::from_yaml(&response.into_string().unwrap()).unwrap();
+    assert!(spec.paths.contains_key("/hello"));
+    assert_eq!(spec.servers[0].url, "/api");
+}
+
+#[test]
+fn format_from_accept_header() {
+    let client = client();
+    let response = client
+        .get("/api/openapi.json")
+        .header(Accept::new([MediaType::new("application", "yaml").into()]))
+        .dispatch();
+    assert_eq!(
+        response.content_type(),
+        Some(ContentType::new("application", "yaml"))
+    );
+
+    let response = client
+        .get("/api/openapi.yaml")
+        .header(Accept::JSON)
+        .dispatch();
+    assert_eq!(response.content_type(), Some(ContentType::JSON));
+    let spec: OpenApi = response.into_json().unwrap();
+    assert!(spec.paths.contains_key("/hello"));
+}